@@ -0,0 +1,271 @@
+//! Contention benchmarks for the public API.
+//!
+//! Run with `cargo bench --bench contention` (or `-- <filter>` for one group).
+//!
+//! - `uncontended`: single-thread acquire + release latency.
+//! - `throughput/{low,high}`: total time for N threads to perform a fixed number
+//!   of increments, with (`low`) or without (`high`) work between acquisitions.
+//! - `spin_vs_yield`: contended acquisition with the default `BackOff`,
+//!   a never-yielding one and an early-yielding one.
+//! - `desync`: 100 threads released together, `SpinLock::lock` vs.
+//!   `SpinLock::lock_desync` (per-thread salted backoff).
+//! - `low_latency`: a tiny critical section under light contention, default
+//!   `SpinLock::new` vs. `SpinLock::new_low_latency`.
+//! - `fenced`: `SpinLock::lock` vs. `SpinLock::lock_fenced`, uncontended and
+//!   under contention. Only runs with `--features fenced-acquire`; the
+//!   difference, if any, shows on weakly ordered targets such as aarch64.
+//!
+//! Every scenario compares against `std::sync::Mutex` where that makes sense.
+
+use axiom_spinlock::{BackOff, RawSpinLock, SpinLock};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::sync::{Arc, Barrier, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const OPS_PER_THREAD: u64 = 10_000;
+const THREAD_COUNTS: [usize; 4] = [1, 2, 4, 8];
+
+/// Simulated work done outside the critical section in the low-contention case.
+fn outside_work() {
+    for i in 0..64u32 {
+        black_box(i);
+    }
+}
+
+/// Runs `threads` workers doing `OPS_PER_THREAD` calls to `op` each, and
+/// returns the time from a common start to the last worker finishing.
+fn run_threads<L: Send + Sync + 'static>(
+    lock: &Arc<L>,
+    threads: usize,
+    op: fn(&L),
+    work: bool,
+) -> Duration {
+    let barrier = Arc::new(Barrier::new(threads + 1));
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            let (lock, barrier) = (lock.clone(), barrier.clone());
+            thread::spawn(move || {
+                barrier.wait();
+                for _ in 0..OPS_PER_THREAD {
+                    op(&lock);
+                    if work {
+                        outside_work();
+                    }
+                }
+            })
+        })
+        .collect();
+    barrier.wait();
+    let start = Instant::now();
+    for h in handles {
+        h.join().unwrap();
+    }
+    start.elapsed()
+}
+
+fn spin_increment(lock: &SpinLock<u64>) {
+    *lock.lock() += 1;
+}
+
+fn desync_increment(lock: &SpinLock<u64>) {
+    *lock.lock_desync() += 1;
+}
+
+fn mutex_increment(lock: &Mutex<u64>) {
+    *lock.lock().unwrap() += 1;
+}
+
+fn uncontended(c: &mut Criterion) {
+    let mut group = c.benchmark_group("uncontended");
+    let spin = SpinLock::new(0u64);
+    group.bench_function("SpinLock", |b| b.iter(|| spin_increment(black_box(&spin))));
+    let mutex = Mutex::new(0u64);
+    group.bench_function("std::sync::Mutex", |b| {
+        b.iter(|| mutex_increment(black_box(&mutex)))
+    });
+    group.finish();
+}
+
+fn throughput(c: &mut Criterion) {
+    for (name, work) in [("throughput/high", false), ("throughput/low", true)] {
+        let mut group = c.benchmark_group(name);
+        group.sample_size(10);
+        for threads in THREAD_COUNTS {
+            group.throughput(Throughput::Elements(threads as u64 * OPS_PER_THREAD));
+
+            let spin = Arc::new(SpinLock::new(0u64));
+            group.bench_with_input(BenchmarkId::new("SpinLock", threads), &threads, |b, &n| {
+                b.iter_custom(|iters| {
+                    (0..iters)
+                        .map(|_| run_threads(&spin, n, spin_increment, work))
+                        .sum()
+                })
+            });
+
+            let mutex = Arc::new(Mutex::new(0u64));
+            group.bench_with_input(
+                BenchmarkId::new("std::sync::Mutex", threads),
+                &threads,
+                |b, &n| {
+                    b.iter_custom(|iters| {
+                        (0..iters)
+                            .map(|_| run_threads(&mutex, n, mutex_increment, work))
+                            .sum()
+                    })
+                },
+            );
+        }
+        group.finish();
+    }
+}
+
+/// A named way of building the per-thread `BackOff`.
+type BackOffVariant = (&'static str, fn() -> BackOff);
+type LockVariant = (&'static str, fn(&SpinLock<u64>));
+
+/// A contended lock loop whose `BackOff` configuration is chosen by the caller.
+fn lock_with(lock: &RawSpinLock, backoff: &BackOff) {
+    while !lock.try_lock() {
+        backoff.wait();
+    }
+    backoff.reset();
+    // Safety: acquired just above.
+    unsafe { lock.unlock() };
+}
+
+fn spin_vs_yield(c: &mut Criterion) {
+    let mut group = c.benchmark_group("spin_vs_yield");
+    group.sample_size(10);
+    let threads = thread::available_parallelism().map_or(4, |n| n.get() * 2);
+
+    let configs: [BackOffVariant; 3] = [
+        ("default", BackOff::new),
+        ("never_yield", || {
+            BackOff::new_with_threshold(BackOff::START_VALUE, u32::MAX)
+        }),
+        ("early_yield", || {
+            BackOff::new_with_threshold(BackOff::START_VALUE, BackOff::START_VALUE)
+        }),
+    ];
+    for (name, make) in configs {
+        let lock = Arc::new(RawSpinLock::new());
+        group.bench_function(BenchmarkId::new(name, threads), |b| {
+            b.iter_custom(|iters| {
+                let mut total = Duration::ZERO;
+                for _ in 0..iters {
+                    let barrier = Arc::new(Barrier::new(threads + 1));
+                    let handles: Vec<_> = (0..threads)
+                        .map(|_| {
+                            let (lock, barrier) = (lock.clone(), barrier.clone());
+                            thread::spawn(move || {
+                                let backoff = make();
+                                barrier.wait();
+                                for _ in 0..OPS_PER_THREAD / 10 {
+                                    lock_with(&lock, &backoff);
+                                }
+                            })
+                        })
+                        .collect();
+                    barrier.wait();
+                    let start = Instant::now();
+                    for h in handles {
+                        h.join().unwrap();
+                    }
+                    total += start.elapsed();
+                }
+                total
+            })
+        });
+    }
+    group.finish();
+}
+
+fn low_latency(c: &mut Criterion) {
+    let mut group = c.benchmark_group("low_latency");
+    group.sample_size(10);
+    for threads in [2, 4] {
+        group.throughput(Throughput::Elements(threads as u64 * OPS_PER_THREAD));
+        let locks = [
+            ("default", Arc::new(SpinLock::new(0u64))),
+            ("new_low_latency", Arc::new(SpinLock::new_low_latency(0u64))),
+        ];
+        for (name, lock) in locks {
+            group.bench_with_input(BenchmarkId::new(name, threads), &threads, |b, &n| {
+                b.iter_custom(|iters| {
+                    (0..iters)
+                        .map(|_| run_threads(&lock, n, spin_increment, true))
+                        .sum()
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+fn desync(c: &mut Criterion) {
+    const THREADS: usize = 100;
+
+    let mut group = c.benchmark_group("desync");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(THREADS as u64 * OPS_PER_THREAD));
+    let variants: [LockVariant; 2] = [("lock", spin_increment), ("lock_desync", desync_increment)];
+    for (name, op) in variants {
+        let lock = Arc::new(SpinLock::new(0u64));
+        group.bench_function(BenchmarkId::new(name, THREADS), |b| {
+            b.iter_custom(|iters| {
+                (0..iters)
+                    .map(|_| run_threads(&lock, THREADS, op, false))
+                    .sum()
+            })
+        });
+    }
+    group.finish();
+}
+
+#[cfg(feature = "fenced-acquire")]
+fn fenced_increment(lock: &SpinLock<u64>) {
+    *lock.lock_fenced() += 1;
+}
+
+fn fenced(c: &mut Criterion) {
+    #[cfg(feature = "fenced-acquire")]
+    {
+        let variants: [LockVariant; 2] =
+            [("lock", spin_increment), ("lock_fenced", fenced_increment)];
+        let mut group = c.benchmark_group("fenced/uncontended");
+        for (name, op) in variants {
+            let lock = SpinLock::new(0u64);
+            group.bench_function(name, |b| b.iter(|| op(black_box(&lock))));
+        }
+        group.finish();
+
+        let mut group = c.benchmark_group("fenced/contended");
+        group.sample_size(10);
+        for threads in THREAD_COUNTS {
+            group.throughput(Throughput::Elements(threads as u64 * OPS_PER_THREAD));
+            for (name, op) in variants {
+                let lock = Arc::new(SpinLock::new(0u64));
+                group.bench_with_input(BenchmarkId::new(name, threads), &threads, |b, &n| {
+                    b.iter_custom(|iters| {
+                        (0..iters).map(|_| run_threads(&lock, n, op, false)).sum()
+                    })
+                });
+            }
+        }
+        group.finish();
+    }
+    #[cfg(not(feature = "fenced-acquire"))]
+    let _ = c;
+}
+
+criterion_group!(
+    benches,
+    uncontended,
+    throughput,
+    spin_vs_yield,
+    desync,
+    low_latency,
+    fenced
+);
+criterion_main!(benches);
@@ -12,8 +12,11 @@
 //! ## Features
 //! - ✅ **`no_std` compatible**
 //! - ⚙️ **Exponential spin delay** via doubling
-//! - 💡 **Optional yielding** (enabled under the `std` feature)
+//! - 💡 **Optional yielding** (enabled under the `std` feature), plus an opt-in
+//!   sleeping tier for long waits
 //! - 🧩 **Configurable starting spin count**
+//! - 📈 **Selectable growth curve** via [`GrowthKind`]
+//! - 🎛️ **One tuning struct**, [`BackOffConfig`], usable from a `static`
 //!
 //! ## Example
 //! ```rust
@@ -37,17 +40,30 @@
 //!
 //! ## Behavior
 //! - Each call to [`BackOff::wait`] spins for a number of iterations determined
-//!   by the internal counter, which doubles after every call up to a fixed limit.
+//!   by the internal counter, which grows after every call up to a fixed limit
+//!   (doubling by default, see [`GrowthKind`]).
 //! - When compiled with the `std` feature, [`std::thread::yield_now`] is called
 //!   once the internal spin count surpasses a yield threshold.
 //! - You can reduce spin intensity with [`BackOff::relax`], or reset to start
 //!   with [`BackOff::reset`].
 //!
+//! ## Clocks
+//! Time-bounded operations such as
+//! [`SpinLock::try_lock_for_ticks`](crate::SpinLock::try_lock_for_ticks) take
+//! a [`Clock`], so the same API works with a hardware tick counter in `no_std`
+//! and with [`InstantClock`] under `std`.
+//!
 //! ## Feature flags
 //! - **`std`** — Enables thread yielding when contention persists beyond
 //!   a configurable threshold.
+//! - **`no-pause`** — Drops the `spin_loop` hint from the wait loop (keeping the
+//!   loop itself), for targets where the pause instruction stalls too long.
+//! - **`metrics`** — Keeps per-instance `BackOffHistory` counters (spin vs.
+//!   yield rounds, resets), readable via `BackOff::history`, counts the spin
+//!   iterations consumed since the last reset (`BackOff::consumed`), and allows
+//!   an escalation callback via `BackOff::on_escalate`.
 
-use core::{cell::Cell, hint::spin_loop};
+use core::cell::Cell;
 
 /// Maximum spin iteration limit.
 const MAX_SPIN: u32 = 1 << 22;
@@ -59,14 +75,142 @@ const START_VALUE: u32 = 1 << 5;
 #[cfg(feature = "std")]
 const YIELD_THRESHOLD: u32 = 1 << 10;
 
+/// Default sleep threshold: the sleep tier is off unless configured.
+#[cfg(feature = "std")]
+const SLEEP_DISABLED: u32 = u32::MAX;
+
+/// First sleep of the sleep tier, in microseconds; it doubles from here.
+#[cfg(feature = "std")]
+const FIRST_SLEEP_MICROS: u32 = 1;
+
 /// Bit shift applied during [`BackOff::relax`] to reduce spin intensity.
 const RELAX_DIV_BIT_VAL: u32 = 1;
 
+/// One iteration of the [`BackOff::wait`] busy loop.
+///
+/// Emits [`core::hint::spin_loop`] by default. With the `no-pause` feature it
+/// emits no pause instruction. Neither keeps the loop alive on its own: on
+/// targets without a pause hint `spin_loop` compiles to nothing, and LLVM
+/// deletes an empty loop of single-thread fences. The iteration counter is
+/// therefore always passed through [`core::hint::black_box`], which the
+/// optimizer must treat as an opaque use, so the loop runs exactly its
+/// iteration count at every optimization level, LTO included.
+#[inline(always)]
+fn pause(iteration: u32) {
+    core::hint::black_box(iteration);
+    #[cfg(not(feature = "no-pause"))]
+    core::hint::spin_loop();
+    #[cfg(feature = "no-pause")]
+    core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+}
+
+/// Spin count of a [`BackOff::wait_jittered`] round: `spin` moved by up to ±25%.
+///
+/// The hash is a multiplicative (Fibonacci) mix of `salt` and `spin`, so nearby
+/// salts such as neighbouring addresses still land far apart.
+#[inline(always)]
+const fn jittered(spin: u32, salt: u32) -> u32 {
+    let mut h = (salt ^ spin.rotate_left(16)).wrapping_mul(0x9E37_79B9);
+    h ^= h >> 15;
+    let span = spin / 2 + 1;
+    (spin - spin / 4).saturating_add(h % span)
+}
+
+/// How the spin count grows after each [`BackOff::wait`].
+///
+/// Whatever the curve, the spin count never exceeds [`BackOff::MAX_SPIN`].
+///
+/// # Examples
+/// ```
+/// use axiom_spinlock::BackOff;
+/// use axiom_spinlock::backoff::GrowthKind;
+///
+/// // Gentle linear escalation: 32, 48, 64, ...
+/// let b = BackOff::new_with_growth(32, GrowthKind::Additive(16));
+/// b.wait();
+/// assert_eq!(b.current(), 48);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrowthKind {
+    /// Doubles the spin count (`spin << 1`). This is the default.
+    Double,
+    /// Adds a fixed number of iterations (`spin + n`). `Additive(0)` keeps the
+    /// spin count constant.
+    Additive(u32),
+    /// Shifts the spin count left by the given number of bits (`spin << n`).
+    ShiftBy(u32),
+}
+
+impl GrowthKind {
+    /// Computes the spin count following `spin`, capped at [`BackOff::MAX_SPIN`].
+    #[inline(always)]
+    const fn next(self, spin: u32) -> u32 {
+        let next = match self {
+            GrowthKind::Double => spin.saturating_mul(2),
+            GrowthKind::Additive(n) => spin.saturating_add(n),
+            // Saturate instead of shifting bits out of the top.
+            GrowthKind::ShiftBy(n) if n >= u32::BITS || spin > (u32::MAX >> n) => u32::MAX,
+            GrowthKind::ShiftBy(n) => spin << n,
+        };
+        if next > MAX_SPIN {
+            MAX_SPIN
+        } else {
+            next
+        }
+    }
+}
+
+/// Tuning parameters for [`BackOff::with_config`].
+///
+/// All fields are public and the type is `const`-constructible, so a config can
+/// be a `static` shared by many backoffs; start from
+/// [`BackOffConfig::DEFAULT`] and override what you need.
+///
+/// # Examples
+/// ```
+/// use axiom_spinlock::backoff::BackOffConfig;
+///
+/// static GENTLE: BackOffConfig = BackOffConfig { max: 1 << 12, ..BackOffConfig::DEFAULT };
+/// assert_eq!(GENTLE.start, BackOffConfig::DEFAULT.start);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackOffConfig {
+    /// Spin count of the first round.
+    pub start: u32,
+    /// Cap of the spin count; values above [`BackOff::MAX_SPIN`] are clamped to it.
+    pub max: u32,
+    /// Left shift applied to the spin count after each round (`1` doubles).
+    pub shift: u32,
+    /// Spin count above which rounds also yield the thread. Only used under
+    /// `std`; `u32::MAX` never yields.
+    pub yield_threshold: u32,
+}
+
+impl BackOffConfig {
+    /// The settings of [`BackOff::new`].
+    pub const DEFAULT: BackOffConfig = BackOffConfig {
+        start: START_VALUE,
+        max: MAX_SPIN,
+        shift: 1,
+        #[cfg(feature = "std")]
+        yield_threshold: YIELD_THRESHOLD,
+        #[cfg(not(feature = "std"))]
+        yield_threshold: u32::MAX,
+    };
+}
+
+impl Default for BackOffConfig {
+    #[inline]
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
 /// A simple exponential backoff manager.
 ///
 /// This struct maintains an internal counter that controls how long to spin
 /// in subsequent retries. Each call to [`wait`](BackOff::wait) increases the
-/// spin duration exponentially (up to [`MAX_SPIN`]), which helps alleviate
+/// spin duration exponentially (up to [`BackOff::MAX_SPIN`]), which helps alleviate
 /// contention under high concurrency.
 ///
 /// On `std` builds, if the spin count grows beyond a threshold, it yields
@@ -93,9 +237,94 @@ const RELAX_DIV_BIT_VAL: u32 = 1;
 /// ```
 pub struct BackOff {
     spin: Cell<u32>,
+    growth: GrowthKind,
+    /// Cap of the spin count, at most [`BackOff::MAX_SPIN`].
+    max_spin: u32,
+    #[cfg(feature = "std")]
+    yield_threshold: u32,
+    /// Spin count above which rounds sleep instead of spinning and yielding.
+    #[cfg(feature = "std")]
+    sleep_threshold: u32,
+    #[cfg(feature = "std")]
+    max_sleep_micros: u32,
+    /// Length of the next sleep-tier round.
+    #[cfg(feature = "std")]
+    sleep_micros: Cell<u32>,
+    #[cfg(feature = "metrics")]
+    history: Cell<BackOffHistory>,
+    /// Called with the new spin count whenever a round escalates it.
+    #[cfg(feature = "metrics")]
+    on_escalate: Cell<Option<fn(u32)>>,
+    /// Spin-loop iterations performed since creation or the last reset.
+    #[cfg(feature = "metrics")]
+    consumed: Cell<u64>,
+}
+
+/// Lifetime counters of a [`BackOff`], returned by [`BackOff::history`].
+///
+/// Only available with the `metrics` feature. Every call to [`BackOff::wait`]
+/// counts exactly once, in `spins`, `yields` or `sleeps`; all counters saturate
+/// instead of wrapping.
+#[cfg(feature = "metrics")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BackOffHistory {
+    /// Rounds that only spun.
+    pub spins: u32,
+    /// Rounds that spun and then yielded the thread (only possible under `std`).
+    pub yields: u32,
+    /// Rounds that slept instead (only possible under `std`, see
+    /// [`BackOff::new_with_sleep`]).
+    pub sleeps: u32,
+    /// Calls to [`BackOff::reset`] or [`BackOff::reset_to`].
+    pub resets: u32,
+}
+
+#[cfg(feature = "metrics")]
+impl BackOffHistory {
+    const fn new() -> Self {
+        BackOffHistory {
+            spins: 0,
+            yields: 0,
+            sleeps: 0,
+            resets: 0,
+        }
+    }
 }
 
 impl BackOff {
+    /// Upper bound of the spin count; no [`GrowthKind`] escalates past it.
+    pub const MAX_SPIN: u32 = MAX_SPIN;
+
+    /// Spin count of [`BackOff::new`], and the value restored by [`reset`](Self::reset).
+    pub const START_VALUE: u32 = START_VALUE;
+
+    /// Default spin count above which [`wait`](Self::wait) also yields the thread.
+    ///
+    /// A round yields when the spin count it *starts* with is strictly greater
+    /// than this value; [`new_with_threshold`](Self::new_with_threshold) overrides
+    /// it per instance. Only defined under the `std` feature.
+    #[cfg(feature = "std")]
+    pub const YIELD_THRESHOLD: u32 = YIELD_THRESHOLD;
+
+    /// A fresh [`BackOff`] with default settings, same as [`BackOff::new`] and
+    /// [`Default::default`].
+    ///
+    /// For const contexts, e.g. `const` items of types embedding a `BackOff` or
+    /// `thread_local!` initializers. `BackOff` is not `Sync`, so it cannot be a
+    /// `static` on its own; each use of the constant is a separate copy.
+    ///
+    /// # Examples
+    /// ```
+    /// use axiom_spinlock::BackOff;
+    ///
+    /// thread_local! {
+    ///     static LOCAL: BackOff = const { BackOff::DEFAULT };
+    /// }
+    /// LOCAL.with(|b| b.wait());
+    /// ```
+    #[allow(clippy::declare_interior_mutable_const)]
+    pub const DEFAULT: BackOff = BackOff::new();
+
     /// Creates a new [`BackOff`] with a default starting spin count.
     ///
     /// # Examples
@@ -105,9 +334,47 @@ impl BackOff {
     /// ```
     #[inline(always)]
     pub const fn new() -> Self {
-        Self {
-            spin: Cell::new(START_VALUE),
+        Self::with_config(&BackOffConfig::DEFAULT)
+    }
+
+    /// Creates a new [`BackOff`] tuned by `config`.
+    ///
+    /// One set of knobs instead of a `new_with_*` constructor per combination.
+    /// The config is copied, so a single `static` can tune any number of
+    /// backoffs. [`reset`](Self::reset) still restores
+    /// [`BackOff::START_VALUE`]; use [`reset_to`](Self::reset_to) with
+    /// `config.start` to restart the configured curve.
+    ///
+    /// # Examples
+    /// ```
+    /// use axiom_spinlock::backoff::BackOffConfig;
+    /// use axiom_spinlock::BackOff;
+    ///
+    /// static TUNING: BackOffConfig = BackOffConfig {
+    ///     start: 8,
+    ///     max: 256,
+    ///     shift: 2,
+    ///     ..BackOffConfig::DEFAULT
+    /// };
+    ///
+    /// let b = BackOff::with_config(&TUNING);
+    /// b.wait();
+    /// assert_eq!(b.current(), 32);
+    /// ```
+    #[inline(always)]
+    pub const fn with_config(config: &BackOffConfig) -> Self {
+        let growth = if config.shift == 1 {
+            GrowthKind::Double
+        } else {
+            GrowthKind::ShiftBy(config.shift)
+        };
+        let mut backoff = Self::new_with_growth(config.start, growth);
+        backoff.max_spin = if config.max < MAX_SPIN { config.max } else { MAX_SPIN };
+        #[cfg(feature = "std")]
+        {
+            backoff.yield_threshold = config.yield_threshold;
         }
+        backoff
     }
 
     /// Creates a new [`BackOff`] with a custom starting spin value.
@@ -121,17 +388,130 @@ impl BackOff {
     /// ```
     #[inline(always)]
     pub const fn new_with(start: u32) -> Self {
+        Self::new_with_growth(start, GrowthKind::Double)
+    }
+
+    /// Creates a new [`BackOff`] with a custom starting spin value and growth curve.
+    ///
+    /// Useful when doubling escalates too aggressively for a workload.
+    ///
+    /// # Examples
+    /// ```
+    /// use axiom_spinlock::BackOff;
+    /// use axiom_spinlock::backoff::GrowthKind;
+    ///
+    /// let b = BackOff::new_with_growth(8, GrowthKind::ShiftBy(2));
+    /// b.wait();
+    /// assert_eq!(b.current(), 32);
+    /// ```
+    #[inline(always)]
+    pub const fn new_with_growth(start: u32, growth: GrowthKind) -> Self {
         Self {
             spin: Cell::new(start),
+            growth,
+            max_spin: MAX_SPIN,
+            #[cfg(feature = "std")]
+            yield_threshold: YIELD_THRESHOLD,
+            #[cfg(feature = "std")]
+            sleep_threshold: SLEEP_DISABLED,
+            #[cfg(feature = "std")]
+            max_sleep_micros: FIRST_SLEEP_MICROS,
+            #[cfg(feature = "std")]
+            sleep_micros: Cell::new(FIRST_SLEEP_MICROS),
+            #[cfg(feature = "metrics")]
+            history: Cell::new(BackOffHistory::new()),
+            #[cfg(feature = "metrics")]
+            on_escalate: Cell::new(None),
+            #[cfg(feature = "metrics")]
+            consumed: Cell::new(0),
         }
     }
 
+    /// Creates a new [`BackOff`] that starts yielding above `yield_threshold`
+    /// instead of [`BackOff::YIELD_THRESHOLD`] (only available with `std`).
+    ///
+    /// A lower threshold yields sooner, trading throughput for latency on
+    /// oversubscribed machines; a higher one keeps spinning longer.
+    /// `u32::MAX` never yields.
+    ///
+    /// # Examples
+    /// ```
+    /// use axiom_spinlock::BackOff;
+    ///
+    /// // Yield as soon as a round spins more than 64 times.
+    /// let b = BackOff::new_with_threshold(32, 64);
+    /// assert_eq!(b.yield_threshold(), 64);
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    pub const fn new_with_threshold(start: u32, yield_threshold: u32) -> Self {
+        let mut backoff = Self::new_with(start);
+        backoff.yield_threshold = yield_threshold;
+        backoff
+    }
+
+    /// Creates a new [`BackOff`] with a third, sleeping tier (only available with `std`).
+    ///
+    /// Escalation becomes spin → spin + yield → sleep: once the spin count
+    /// exceeds `sleep_threshold` (which should be above
+    /// [`BackOff::YIELD_THRESHOLD`]), each round stops spinning and instead calls
+    /// `std::thread::sleep`, starting at 1µs and doubling up to `max_sleep`.
+    ///
+    /// # Latency tradeoff
+    /// Spinning and yielding notice a release within nanoseconds to microseconds,
+    /// but burn a core the whole time. A sleeping waiter uses almost no CPU, yet
+    /// only notices a release once its sleep ends — plus the OS timer slack,
+    /// often 50µs or more — so a lock released mid-sleep stays idle for up to
+    /// about `max_sleep`. Pick `sleep_threshold` so that only waits already
+    /// known to be long (millisecond-scale holds) reach this tier, and keep
+    /// `max_sleep` at the worst extra latency you can accept. Without this
+    /// constructor the tier is disabled.
+    ///
+    /// # Examples
+    /// ```
+    /// use axiom_spinlock::BackOff;
+    /// use std::time::Duration;
+    ///
+    /// let b = BackOff::new_with_sleep(32, 1 << 14, Duration::from_micros(500));
+    /// b.wait(); // short waits still spin
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    pub const fn new_with_sleep(
+        start: u32,
+        sleep_threshold: u32,
+        max_sleep: std::time::Duration,
+    ) -> Self {
+        let micros = max_sleep.as_micros();
+        let mut backoff = Self::new_with(start);
+        backoff.sleep_threshold = sleep_threshold;
+        backoff.max_sleep_micros = if micros > u32::MAX as u128 {
+            u32::MAX
+        } else if micros == 0 {
+            FIRST_SLEEP_MICROS
+        } else {
+            micros as u32
+        };
+        backoff
+    }
+
+    /// Returns the spin count above which [`wait`](Self::wait) yields (only available with `std`).
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    pub fn yield_threshold(&self) -> u32 {
+        self.yield_threshold
+    }
+
     /// Performs a backoff wait by spinning for a short, increasing duration.
     ///
-    /// The number of spin iterations doubles each time (up to [`MAX_SPIN`]).
+    /// The number of spin iterations grows each time according to the configured
+    /// [`GrowthKind`] (doubling by default, up to [`BackOff::MAX_SPIN`]).
     /// Under the `std` feature, this method also calls [`std::thread::yield_now`]
     /// when contention persists beyond a threshold.
     ///
+    /// Only the dispatch is inlined; the spin loop itself is an out-of-line
+    /// `#[cold]` function, keeping callers' uncontended paths small.
+    ///
     /// # Examples
     /// ```ignore
     /// use axiom_spinlock::BackOff;
@@ -141,18 +521,232 @@ impl BackOff {
     /// ```
     #[inline(always)]
     pub fn wait(&self) {
+        self.wait_round(self.spin.get());
+    }
+
+    /// Like [`wait`](Self::wait), but perturbs this round's spin count by `salt`.
+    ///
+    /// Threads that start contending at the same moment with identical backoffs
+    /// retry in lockstep and keep colliding. Salting each waiter differently (e.g.
+    /// with the lock's address, a thread id or a CPU number) spreads their retries
+    /// without storing any PRNG state: the round spins between roughly 75% and
+    /// 125% of [`current`](Self::current), chosen by a cheap hash of the salt and
+    /// the current spin count.
+    ///
+    /// The jitter is **deterministic**: the same salt with the same spin count
+    /// always spins the same amount, so waiters sharing a salt stay in lockstep.
+    /// Escalation is unaffected — the next spin count grows from the unjittered
+    /// value, exactly as after [`wait`](Self::wait).
+    ///
+    /// # Examples
+    /// ```
+    /// use axiom_spinlock::BackOff;
+    ///
+    /// let lock_word = 0u8;
+    /// let b = BackOff::new();
+    /// b.wait_jittered(&lock_word as *const u8 as usize as u32);
+    /// assert_eq!(b.current(), BackOff::START_VALUE * 2);
+    /// ```
+    #[inline]
+    pub fn wait_jittered(&self, salt: u32) {
         let end = self.spin.get();
+        self.wait_round(jittered(end, salt));
+    }
+
+    /// One backoff round: spins `iterations` times, then escalates from the
+    /// current spin count and, under `std`, yields if it exceeded the threshold
+    /// (or sleeps instead of spinning at all past the sleep threshold).
+    ///
+    /// This is the contended slow path, kept out of line on purpose: `wait` and
+    /// `wait_jittered` stay tiny inlined dispatchers, so a `lock()` fast path that
+    /// never waits only carries a call instruction instead of the whole loop and
+    /// yield logic. In a release build, `RawSpinLock::lock_counted` compiles to
+    /// the swap loop plus a `call` to this function (check with
+    /// `cargo rustc --release --lib -- --emit asm`).
+    #[cold]
+    #[inline(never)]
+    fn wait_round(&self, iterations: u32) {
+        let end = self.spin.get();
+
+        #[cfg(feature = "std")]
+        if end > self.sleep_threshold {
+            self.escalate(end);
+            self.sleep_round();
+            return;
+        }
+
+        for i in 0..iterations {
+            pause(i);
+        }
+        self.finish_round(end, iterations);
+    }
+
+    /// Like [`wait`](Self::wait), but polls `busy` between spin iterations and
+    /// returns `false` as soon as it reports `false`, cutting the round short.
+    ///
+    /// A cut-short round neither escalates nor yields: whatever the caller was
+    /// waiting for is ready, so it should act on it right away. Returns `true`
+    /// after a full round (escalated as usual). Sleep-tier rounds cannot be
+    /// interrupted and behave exactly like `wait`.
+    #[inline]
+    pub(crate) fn wait_while(&self, busy: impl Fn() -> bool) -> bool {
+        let end = self.spin.get();
+
+        #[cfg(feature = "std")]
+        if end > self.sleep_threshold {
+            self.wait_round(end);
+            return true;
+        }
 
-        for _ in 0..end {
-            spin_loop();
+        for i in 0..end {
+            if !busy() {
+                #[cfg(feature = "metrics")]
+                self.consumed.set(self.consumed.get().saturating_add(u64::from(i)));
+                return false;
+            }
+            pause(i);
         }
+        self.finish_round(end, end);
+        true
+    }
+
+    /// The end of a full spin round of `iterations`: escalates from `end` and,
+    /// under `std`, yields past the threshold.
+    #[inline(always)]
+    fn finish_round(&self, end: u32, iterations: u32) {
+        #[cfg(feature = "metrics")]
+        self.consumed.set(self.consumed.get().saturating_add(u64::from(iterations)));
+        #[cfg(not(feature = "metrics"))]
+        let _ = iterations;
 
-        self.spin.set((end << 1).min(MAX_SPIN));
+        self.escalate(end);
 
         #[cfg(feature = "std")]
-        if end > YIELD_THRESHOLD {
+        let yielded = end > self.yield_threshold;
+        #[cfg(not(feature = "std"))]
+        let yielded = false;
+
+        if yielded {
+            #[cfg(feature = "std")]
             std::thread::yield_now();
         }
+
+        #[cfg(feature = "metrics")]
+        self.record(|h| {
+            if yielded {
+                h.yields = h.yields.saturating_add(1);
+            } else {
+                h.spins = h.spins.saturating_add(1);
+            }
+        });
+    }
+
+    /// Grows the spin count from `end`, reporting the step to the
+    /// [`on_escalate`](Self::on_escalate) callback under `metrics`.
+    #[inline(always)]
+    fn escalate(&self, end: u32) {
+        let next = self.growth.next(end);
+        let next = if next > self.max_spin { self.max_spin } else { next };
+        self.spin.set(next);
+        #[cfg(feature = "metrics")]
+        if next != end {
+            if let Some(callback) = self.on_escalate.get() {
+                callback(next);
+            }
+        }
+    }
+
+    /// One round of the sleep tier: sleeps, then doubles the next sleep up to the cap.
+    #[cfg(feature = "std")]
+    fn sleep_round(&self) {
+        let micros = self.sleep_micros.get();
+        std::thread::sleep(std::time::Duration::from_micros(micros as u64));
+        self.sleep_micros.set(micros.saturating_mul(2).min(self.max_sleep_micros));
+
+        #[cfg(feature = "metrics")]
+        self.record(|h| h.sleeps = h.sleeps.saturating_add(1));
+    }
+
+    /// Busy-waits for `cycles` iterations of [`core::hint::spin_loop`], touching
+    /// no backoff state.
+    ///
+    /// A stateless, one-shot micro-delay (e.g. between two atomic operations),
+    /// unlike [`wait`](Self::wait), which escalates. It is purely a busy-wait of
+    /// approximately `cycles` pause instructions: how long that takes depends on
+    /// the CPU, so calibrate before relying on a wall-clock duration. Never
+    /// yields or sleeps, and works in `no_std`. With the `no-pause` feature the
+    /// iterations carry no pause hint, like every other wait loop in this crate.
+    ///
+    /// # Examples
+    /// ```
+    /// use axiom_spinlock::BackOff;
+    ///
+    /// BackOff::pause(64);
+    /// ```
+    #[inline]
+    pub fn pause(cycles: u32) {
+        for i in 0..cycles {
+            pause(i);
+        }
+    }
+
+    /// Returns how many [`pause`](Self::pause) iterations take roughly one
+    /// microsecond on this CPU (only available with `std`).
+    ///
+    /// The first call measures it, timing a few batches of pause iterations
+    /// against `std::time::Instant` and keeping the fastest; the result is
+    /// cached for the rest of the process, so later calls are a single load.
+    /// Expect the first call to take a few milliseconds.
+    ///
+    /// The value is **approximate**: the cost of a pause instruction varies
+    /// between CPU models (from a few to over a hundred cycles), and frequency
+    /// scaling, turbo states, SMT siblings and migration between cores all move
+    /// it after calibration. Treat spin counts derived from it as rough time
+    /// units, never as a timer. It is always at least 1.
+    ///
+    /// # Examples
+    /// ```
+    /// use axiom_spinlock::BackOff;
+    ///
+    /// let per_micro = BackOff::calibrate();
+    /// assert!(per_micro >= 1);
+    /// assert_eq!(BackOff::calibrate(), per_micro); // cached
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn calibrate() -> u32 {
+        static PAUSES_PER_MICRO: std::sync::OnceLock<u32> = std::sync::OnceLock::new();
+
+        *PAUSES_PER_MICRO.get_or_init(|| {
+            const BATCH: u32 = 1 << 14;
+            const ROUNDS: u32 = 5;
+            let mut fastest = std::time::Duration::MAX;
+            for _ in 0..ROUNDS {
+                let start = std::time::Instant::now();
+                Self::pause(BATCH);
+                fastest = fastest.min(start.elapsed());
+            }
+            let nanos = fastest.as_nanos().max(1);
+            (BATCH as u128 * 1_000 / nanos).clamp(1, u32::MAX as u128) as u32
+        })
+    }
+
+    /// Busy-waits for roughly `micros` microseconds, using the
+    /// [`calibrate`](Self::calibrate)d pause rate (only available with `std`).
+    ///
+    /// No clock is read while waiting, which keeps it usable as a cheap,
+    /// time-scaled delay; the duration is only as accurate as the calibration
+    /// (see its caveats). The first call may also pay for calibrating.
+    ///
+    /// # Examples
+    /// ```
+    /// use axiom_spinlock::BackOff;
+    ///
+    /// BackOff::wait_micros(5);
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn wait_micros(micros: u32) {
+        Self::pause(Self::calibrate().saturating_mul(micros));
     }
 
     /// Reduces the current spin intensity by a fixed shift.
@@ -180,16 +774,209 @@ impl BackOff {
         self.spin.get()
     }
 
+    /// Returns how many `spin_loop` iterations the next [`wait`](Self::wait) performs.
+    ///
+    /// This is the same value as [`current`](Self::current), named for code that
+    /// reasons about the escalation curve against [`BackOff::MAX_SPIN`] and
+    /// [`BackOff::YIELD_THRESHOLD`].
+    ///
+    /// # Examples
+    /// ```
+    /// use axiom_spinlock::BackOff;
+    ///
+    /// let b = BackOff::new();
+    /// assert_eq!(b.spin_loop_iterations(), BackOff::START_VALUE);
+    /// b.wait();
+    /// assert_eq!(b.spin_loop_iterations(), BackOff::START_VALUE * 2);
+    /// assert!(b.spin_loop_iterations() <= BackOff::MAX_SPIN);
+    /// ```
+    #[inline(always)]
+    pub fn spin_loop_iterations(&self) -> u32 {
+        self.spin.get()
+    }
+
+    /// Returns the spin count the next [`wait`](Self::wait) escalates to, without
+    /// waiting.
+    ///
+    /// The next `wait` spins [`current`](Self::current) iterations and leaves
+    /// this value behind: the configured [`GrowthKind`] applied once (doubling by
+    /// default), capped at the maximum. Together with
+    /// [`would_yield`](Self::would_yield) it lets a trace log each escalation
+    /// decision before it is taken.
+    ///
+    /// # Examples
+    /// ```
+    /// use axiom_spinlock::BackOff;
+    ///
+    /// let b = BackOff::new();
+    /// let planned = b.peek_next();
+    /// assert_eq!(planned, BackOff::START_VALUE * 2);
+    /// b.wait();
+    /// assert_eq!(b.current(), planned);
+    /// ```
+    #[inline]
+    pub fn peek_next(&self) -> u32 {
+        let next = self.growth.next(self.spin.get());
+        if next > self.max_spin {
+            self.max_spin
+        } else {
+            next
+        }
+    }
+
+    /// Returns whether the next [`wait`](Self::wait) yields the thread after
+    /// spinning (only available with `std`).
+    ///
+    /// `true` when [`current`](Self::current) is above the
+    /// [`yield_threshold`](Self::yield_threshold). A round of the
+    /// [sleep tier](Self::new_with_sleep) sleeps instead, so this returns `false`
+    /// for it.
+    ///
+    /// # Examples
+    /// ```
+    /// use axiom_spinlock::BackOff;
+    ///
+    /// let b = BackOff::new_with_threshold(32, 64);
+    /// assert!(!b.would_yield());
+    /// b.wait(); // 32 -> 64
+    /// assert!(!b.would_yield());
+    /// b.wait(); // 64 -> 128
+    /// assert!(b.would_yield());
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn would_yield(&self) -> bool {
+        let end = self.spin.get();
+        end <= self.sleep_threshold && end > self.yield_threshold
+    }
+
     /// Resets the backoff spin count to the default starting value.
     #[inline(always)]
     pub fn reset(&self) {
-        self.spin.set(START_VALUE);
+        self.reset_to(START_VALUE);
     }
 
     /// Resets the backoff spin count to a specified value.
     #[inline(always)]
     pub fn reset_to(&self, spin: u32) {
         self.spin.set(spin);
+        #[cfg(feature = "std")]
+        self.sleep_micros.set(FIRST_SLEEP_MICROS);
+        #[cfg(feature = "metrics")]
+        {
+            self.record(|h| h.resets = h.resets.saturating_add(1));
+            self.consumed.set(0);
+        }
+    }
+
+    /// Returns the spin-loop iterations performed since the backoff was created
+    /// or last [`reset`](Self::reset) (only available with `metrics`).
+    ///
+    /// A cooperative scheduler can charge this against a task's work budget and
+    /// preempt a task that spins too long. Sleep-tier rounds spin nothing and
+    /// add nothing; the count saturates instead of wrapping.
+    ///
+    /// # Examples
+    /// ```
+    /// use axiom_spinlock::BackOff;
+    ///
+    /// let b = BackOff::new();
+    /// b.wait();
+    /// b.wait();
+    /// assert_eq!(b.consumed(), u64::from(BackOff::START_VALUE * 3));
+    /// b.reset();
+    /// assert_eq!(b.consumed(), 0);
+    /// ```
+    #[cfg(feature = "metrics")]
+    #[inline]
+    pub fn consumed(&self) -> u64 {
+        self.consumed.get()
+    }
+
+    /// Returns how the escalation played out so far (only available with `metrics`).
+    ///
+    /// Useful to calibrate the yield threshold for a contended region: log the
+    /// history when leaving the region, then [`reset_history`](Self::reset_history).
+    ///
+    /// # Examples
+    /// ```
+    /// use axiom_spinlock::BackOff;
+    ///
+    /// let b = BackOff::new();
+    /// b.wait();
+    /// b.reset();
+    /// let h = b.history();
+    /// assert_eq!((h.spins, h.yields, h.resets), (1, 0, 1));
+    /// ```
+    #[cfg(feature = "metrics")]
+    #[inline]
+    pub fn history(&self) -> BackOffHistory {
+        self.history.get()
+    }
+
+    /// Clears the counters returned by [`history`](Self::history).
+    #[cfg(feature = "metrics")]
+    #[inline]
+    pub fn reset_history(&self) {
+        self.history.set(BackOffHistory::new());
+    }
+
+    /// Registers `callback` to be called with the new spin count each time a
+    /// [`wait`](Self::wait) round escalates it (only available with `metrics`).
+    ///
+    /// Rounds that stay at [`BackOff::MAX_SPIN`] do not call it, and a later
+    /// call replaces the previous callback. It runs synchronously on the waiting
+    /// thread, inside the backoff loop and while the caller is still contending,
+    /// so keep it cheap and lock-free: bump a counter or push to a preallocated
+    /// buffer, never take the lock being waited on. Without `metrics` neither this
+    /// method nor the check exists, so there is no cost when unused.
+    ///
+    /// # Examples
+    /// ```
+    /// use axiom_spinlock::BackOff;
+    /// use std::sync::atomic::{AtomicU32, Ordering::Relaxed};
+    ///
+    /// static LAST: AtomicU32 = AtomicU32::new(0);
+    ///
+    /// let b = BackOff::new();
+    /// b.on_escalate(|spin| LAST.store(spin, Relaxed));
+    /// b.wait();
+    /// assert_eq!(LAST.load(Relaxed), BackOff::START_VALUE * 2);
+    /// ```
+    #[cfg(feature = "metrics")]
+    #[inline]
+    pub fn on_escalate(&self, callback: fn(u32)) {
+        self.on_escalate.set(Some(callback));
+    }
+
+    #[cfg(feature = "metrics")]
+    #[inline(always)]
+    fn record(&self, update: impl FnOnce(&mut BackOffHistory)) {
+        let mut history = self.history.get();
+        update(&mut history);
+        self.history.set(history);
+    }
+
+    /// Inherits the escalation state of another [`BackOff`].
+    ///
+    /// Sets this backoff's spin count to the **maximum** of its own and `other`'s,
+    /// so an inner retry loop entered from an already hot outer loop starts at the
+    /// outer escalation level instead of falling back to the starting value. The
+    /// counts are not summed: taking over never escalates beyond what either
+    /// loop had already reached. `other` is left unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// use axiom_spinlock::BackOff;
+    ///
+    /// let outer = BackOff::new_with(1024);
+    /// let inner = BackOff::new_with(64);
+    /// inner.take_over(&outer);
+    /// assert_eq!(inner.current(), 1024);
+    /// ```
+    #[inline(always)]
+    pub fn take_over(&self, other: &BackOff) {
+        self.spin.set(self.spin.get().max(other.spin.get()));
     }
 
     /// Explicitly yields the current thread (only available with `std`).
@@ -200,8 +987,143 @@ impl BackOff {
     pub fn yield_now(&self) {
         std::thread::yield_now();
     }
+
+    /// Yields to the OS scheduler directly (only available with `std`).
+    ///
+    /// With the `libc` feature on Unix this calls `sched_yield(2)` itself;
+    /// everywhere else it is [`yield_now`](Self::yield_now).
+    /// [`wait`](Self::wait) always escalates with `std::thread::yield_now`.
+    ///
+    /// # Platform differences
+    /// - **Unix:** `std::thread::yield_now` is itself a `sched_yield` call, so
+    ///   the direct call only skips std's wrapper and makes the syscall
+    ///   explicit for profiling. No Unix call can yield *to* the lock holder:
+    ///   under Linux's CFS/EEVDF, `sched_yield` only gives up the rest of the
+    ///   time slice to whatever the scheduler picks next on this CPU, and under
+    ///   `SCHED_FIFO`/`SCHED_RR` it only lets threads of equal priority run.
+    /// - **Windows:** `yield_now` is `SwitchToThread`, which yields only to
+    ///   threads ready on the current processor.
+    /// - Benchmark on the target: the effect depends on the scheduler and load.
+    ///
+    /// # Examples
+    /// ```
+    /// use axiom_spinlock::BackOff;
+    ///
+    /// let b = BackOff::new();
+    /// b.yield_to_os();
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn yield_to_os(&self) {
+        #[cfg(all(feature = "libc", unix))]
+        // Safety: `sched_yield` takes no arguments and has no preconditions;
+        // it cannot fail on Linux and its only error elsewhere is ignorable.
+        unsafe {
+            libc::sched_yield();
+        }
+        #[cfg(not(all(feature = "libc", unix)))]
+        std::thread::yield_now();
+    }
+}
+
+impl Default for BackOff {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A monotonic tick source for time-bounded operations.
+///
+/// `no_std` has no clock, so time-bounded locking takes one from the caller:
+/// a hardware cycle counter, a timer peripheral, or [`InstantClock`] under
+/// `std`. Ticks are opaque; a deadline just has to be expressed in the same
+/// unit as `now`. Any `Fn() -> u64` closure is a clock.
+///
+/// # Example
+/// ```
+/// use axiom_spinlock::backoff::Clock;
+/// use axiom_spinlock::SpinLock;
+/// use core::cell::Cell;
+///
+/// struct FakeClock(Cell<u64>);
+///
+/// impl Clock for FakeClock {
+///     fn now(&self) -> u64 {
+///         self.0.set(self.0.get() + 1);
+///         self.0.get()
+///     }
+/// }
+///
+/// let lock = SpinLock::new(0);
+/// let _held = lock.lock();
+/// assert!(lock.try_lock_for_ticks(10, &FakeClock(Cell::new(0))).is_none());
+/// ```
+pub trait Clock {
+    /// Returns the current time in ticks; must never go backwards.
+    fn now(&self) -> u64;
+}
+
+impl<F: Fn() -> u64> Clock for F {
+    #[inline(always)]
+    fn now(&self) -> u64 {
+        self()
+    }
 }
 
+/// A [`Clock`] backed by [`std::time::Instant`], ticking in nanoseconds since
+/// its creation (only available with `std`).
+///
+/// # Example
+/// ```
+/// use axiom_spinlock::backoff::InstantClock;
+/// use axiom_spinlock::SpinLock;
+/// use std::time::Duration;
+///
+/// let clock = InstantClock::new();
+/// let lock = SpinLock::new(0);
+/// let deadline = clock.deadline_after(Duration::from_millis(1));
+/// assert!(lock.try_lock_for_ticks(deadline, &clock).is_some());
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy)]
+pub struct InstantClock {
+    origin: std::time::Instant,
+}
+
+#[cfg(feature = "std")]
+impl InstantClock {
+    /// Creates a clock reading 0 now.
+    #[inline]
+    pub fn new() -> Self {
+        InstantClock {
+            origin: std::time::Instant::now(),
+        }
+    }
+
+    /// Returns the tick `timeout` from now, for use as a deadline.
+    #[inline]
+    pub fn deadline_after(&self, timeout: std::time::Duration) -> u64 {
+        let timeout = u64::try_from(timeout.as_nanos()).unwrap_or(u64::MAX);
+        self.now().saturating_add(timeout)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for InstantClock {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clock for InstantClock {
+    #[inline]
+    fn now(&self) -> u64 {
+        u64::try_from(self.origin.elapsed().as_nanos()).unwrap_or(u64::MAX)
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -254,4 +1176,356 @@ mod tests {
 
         assert!(after < before, "Relax did not reduce spin intensity");
     }
+
+    /// Ensures that each growth curve escalates as documented and saturates at MAX_SPIN.
+    #[test]
+    fn test_growth_kinds() {
+        let additive = BackOff::new_with_growth(10, GrowthKind::Additive(5));
+        additive.wait();
+        additive.wait();
+        assert_eq!(additive.current(), 20, "Additive growth should add a fixed step");
+
+        let constant = BackOff::new_with_growth(4, GrowthKind::Additive(0));
+        constant.wait();
+        assert_eq!(constant.current(), 4, "Additive(0) should keep the spin constant");
+
+        let shifted = BackOff::new_with_growth(1, GrowthKind::ShiftBy(3));
+        shifted.wait();
+        assert_eq!(shifted.current(), 8, "ShiftBy(3) should multiply by 8");
+
+        assert_eq!(GrowthKind::ShiftBy(40).next(1), MAX_SPIN);
+        assert_eq!(GrowthKind::Double.next(MAX_SPIN), MAX_SPIN);
+        assert_eq!(GrowthKind::Additive(u32::MAX).next(MAX_SPIN), MAX_SPIN);
+    }
+
+    /// Ensures that take_over adopts the larger spin count and leaves the source alone.
+    #[test]
+    fn test_take_over_uses_max() {
+        let outer = BackOff::new();
+        for _ in 0..4 {
+            outer.wait();
+        }
+        let hot = outer.current();
+
+        let inner = BackOff::new();
+        inner.take_over(&outer);
+        assert_eq!(inner.current(), hot, "Inner loop should inherit the outer escalation");
+        assert_eq!(outer.current(), hot, "take_over must not modify the source");
+
+        // A hotter backoff keeps its own state rather than summing.
+        let hotter = BackOff::new_with(hot * 4);
+        hotter.take_over(&outer);
+        assert_eq!(hotter.current(), hot * 4);
+    }
+
+    /// Ensures that metrics history accumulates across wait/reset cycles.
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_history_accumulates() {
+        let b = BackOff::new();
+
+        for _ in 0..3 {
+            b.wait();
+            b.wait();
+            b.reset();
+        }
+        let h = b.history();
+        assert_eq!(h.spins + h.yields, 6, "Every wait should be counted once");
+        assert_eq!(h.resets, 3);
+
+        // Escalate far enough to cross the yield threshold (std only).
+        #[cfg(feature = "std")]
+        {
+            b.reset_to(BackOff::YIELD_THRESHOLD + 1);
+            b.wait();
+            assert_eq!(b.history().yields, 1, "Wait above threshold should yield");
+        }
+
+        b.reset_history();
+        assert_eq!(b.history(), BackOffHistory::default());
+    }
+
+    /// Ensures that the public constants describe the actual escalation curve.
+    #[test]
+    fn test_public_constants_match_behavior() {
+        let b = BackOff::new();
+        assert_eq!(b.spin_loop_iterations(), BackOff::START_VALUE);
+
+        b.reset_to(BackOff::MAX_SPIN);
+        b.wait();
+        assert_eq!(b.spin_loop_iterations(), BackOff::MAX_SPIN, "Spin count should cap");
+
+        #[cfg(feature = "std")]
+        const {
+            assert!(BackOff::START_VALUE < BackOff::YIELD_THRESHOLD);
+            assert!(BackOff::YIELD_THRESHOLD < BackOff::MAX_SPIN);
+        }
+    }
+
+    /// Ensures that the wait loop really burns its iterations (also with `no-pause`).
+    ///
+    /// Only meaningful in optimized builds: run it with `cargo test --release`.
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_wait_loop_not_elided() {
+        use std::time::{Duration, Instant};
+
+        let b = BackOff::new_with(BackOff::MAX_SPIN);
+        let start = Instant::now();
+        b.wait();
+        // Millions of iterations cannot finish in 100µs unless the loop was removed.
+        assert!(start.elapsed() >= Duration::from_micros(100));
+    }
+
+    /// Ensures that jitter depends on the salt, stays bounded and is deterministic.
+    #[test]
+    fn test_jitter_varies_with_salt() {
+        let spin = 1 << 10;
+        let durations: Vec<u32> = (0..8u32).map(|salt| jittered(spin, salt * 64)).collect();
+
+        let mut distinct = durations.clone();
+        distinct.sort_unstable();
+        distinct.dedup();
+        assert!(distinct.len() > 1, "Different salts should spin differently");
+        assert!(durations.iter().all(|&d| d >= spin - spin / 4 && d <= spin + spin / 4));
+        assert_eq!(jittered(spin, 64), jittered(spin, 64), "Jitter must be deterministic");
+
+        // Escalation follows the unjittered count.
+        let b = BackOff::new();
+        b.wait_jittered(0xDEAD_BEEF);
+        assert_eq!(b.current(), START_VALUE * 2);
+    }
+
+    /// Ensures that a low yield threshold yields after fewer rounds than the default.
+    #[cfg(all(feature = "std", feature = "metrics"))]
+    #[test]
+    fn test_low_threshold_yields_sooner() {
+        let rounds_until_yield = |b: &BackOff| {
+            let mut rounds = 0;
+            while b.history().yields == 0 {
+                b.wait();
+                rounds += 1;
+            }
+            rounds
+        };
+
+        let default = BackOff::new();
+        let eager = BackOff::new_with_threshold(START_VALUE, START_VALUE * 2);
+        assert_eq!(default.yield_threshold(), YIELD_THRESHOLD);
+
+        let eager_rounds = rounds_until_yield(&eager);
+        let default_rounds = rounds_until_yield(&default);
+        assert!(eager_rounds < default_rounds, "{eager_rounds} vs {default_rounds}");
+        assert_eq!(eager_rounds, 3, "32 and 64 only spin, 128 > 64 yields");
+    }
+
+    /// Ensures that the sleep tier is only reached after prolonged contention.
+    #[cfg(all(feature = "std", feature = "metrics"))]
+    #[test]
+    fn test_sleep_tier_after_prolonged_contention() {
+        use std::time::Duration;
+
+        let b = BackOff::new_with_sleep(START_VALUE, 1 << 12, Duration::from_micros(50));
+        while b.current() <= 1 << 12 {
+            b.wait();
+            assert_eq!(b.history().sleeps, 0, "Slept before crossing the threshold");
+        }
+        assert!(b.history().yields > 0, "Yield tier should come before the sleep tier");
+
+        for _ in 0..8 {
+            b.wait();
+        }
+        assert_eq!(b.history().sleeps, 8);
+        assert_eq!(b.sleep_micros.get(), 50, "Sleeps should double up to the cap");
+
+        // The default has no sleep tier, even at the maximum spin count.
+        let default = BackOff::new_with(MAX_SPIN);
+        default.wait();
+        assert_eq!(default.history().sleeps, 0);
+    }
+
+    /// Ensures that the one-shot pause leaves escalation state untouched.
+    #[test]
+    fn test_pause_is_stateless() {
+        let b = BackOff::new();
+        b.wait();
+        let before = b.current();
+        BackOff::pause(1_000);
+        BackOff::pause(0);
+        assert_eq!(b.current(), before, "pause must not escalate any BackOff");
+    }
+
+    /// Ensures that on_escalate reports each new spin count and stays quiet once saturated.
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_on_escalate_reports_curve() {
+        use core::sync::atomic::{AtomicU32, Ordering::Relaxed};
+
+        static CALLS: AtomicU32 = AtomicU32::new(0);
+        static LAST: AtomicU32 = AtomicU32::new(0);
+
+        let b = BackOff::new_with(BackOff::MAX_SPIN >> 2);
+        b.on_escalate(|spin| {
+            CALLS.fetch_add(1, Relaxed);
+            LAST.store(spin, Relaxed);
+        });
+        b.wait();
+        assert_eq!(LAST.load(Relaxed), BackOff::MAX_SPIN >> 1);
+        b.wait();
+        assert_eq!(LAST.load(Relaxed), BackOff::MAX_SPIN);
+        // Saturated: the round does not escalate, so no call.
+        b.wait();
+        assert_eq!(CALLS.load(Relaxed), 2);
+    }
+
+    /// Ensures that Default and BackOff::DEFAULT start like BackOff::new.
+    #[test]
+    fn test_default_matches_new() {
+        fn make<B: Default>() -> B {
+            B::default()
+        }
+
+        let generic: BackOff = make();
+        assert_eq!(generic.current(), BackOff::new().current());
+        let constant = BackOff::DEFAULT;
+        assert_eq!(constant.current(), START_VALUE);
+        constant.wait();
+        // Each use of the constant starts fresh.
+        let again = BackOff::DEFAULT;
+        assert_eq!(again.current(), START_VALUE);
+    }
+
+    /// Ensures calibration is plausible and that `wait_micros` is roughly time-scaled.
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_calibrate_plausible() {
+        use std::time::{Duration, Instant};
+
+        let per_micro = BackOff::calibrate();
+        // At least one pause per µs, and no CPU pauses 100k times in a µs.
+        assert!((1..100_000).contains(&per_micro), "per_micro = {per_micro}");
+        assert_eq!(BackOff::calibrate(), per_micro);
+
+        let start = Instant::now();
+        BackOff::wait_micros(2_000);
+        let elapsed = start.elapsed();
+        // Very loose: only rule out "no wait at all" (preemption can stretch it).
+        assert!(elapsed >= Duration::from_micros(200), "elapsed = {elapsed:?}");
+    }
+
+    /// Ensures that InstantClock advances with real time and saturates far deadlines.
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_instant_clock_is_monotonic() {
+        use std::time::Duration;
+
+        let clock = InstantClock::new();
+        let first = clock.now();
+        std::thread::sleep(Duration::from_millis(1));
+        let second = clock.now();
+        assert!(second >= first + 1_000_000, "{first} -> {second}");
+
+        let deadline = clock.deadline_after(Duration::from_secs(1));
+        assert!(deadline >= second + 1_000_000_000);
+        assert_eq!(clock.deadline_after(Duration::MAX), u64::MAX);
+    }
+
+    /// Ensures that a custom config sets the start, shift, cap and yield threshold.
+    #[test]
+    fn test_with_config_curve() {
+        static CONFIG: BackOffConfig = BackOffConfig {
+            start: 4,
+            max: 100,
+            shift: 3,
+            yield_threshold: 50,
+        };
+
+        let b = BackOff::with_config(&CONFIG);
+        let mut curve = [0; 4];
+        for step in curve.iter_mut() {
+            *step = b.current();
+            b.wait();
+        }
+        assert_eq!(curve, [4, 32, 100, 100], "shift by 3, then capped at max");
+        #[cfg(feature = "std")]
+        assert_eq!(b.yield_threshold(), 50);
+
+        let huge = BackOff::with_config(&BackOffConfig { start: MAX_SPIN, max: u32::MAX, ..CONFIG });
+        huge.wait();
+        assert_eq!(huge.current(), MAX_SPIN, "max is clamped to MAX_SPIN");
+    }
+
+    /// Ensures that `new()` is exactly the default config.
+    #[test]
+    fn test_default_config_matches_new() {
+        assert_eq!(BackOffConfig::default(), BackOffConfig::DEFAULT);
+        let (a, b) = (BackOff::new(), BackOff::with_config(&BackOffConfig::DEFAULT));
+        for _ in 0..8 {
+            assert_eq!(a.current(), b.current());
+            a.wait();
+            b.wait();
+        }
+        #[cfg(feature = "std")]
+        assert_eq!(BackOff::new().yield_threshold(), BackOff::YIELD_THRESHOLD);
+    }
+
+    /// Ensures that `yield_to_os` returns and leaves the spin state alone.
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_yield_to_os() {
+        let b = BackOff::new();
+        b.wait();
+        let before = b.current();
+        for _ in 0..16 {
+            b.yield_to_os();
+        }
+        assert_eq!(b.current(), before);
+    }
+
+    /// Ensures that `consumed` sums every round's iterations and clears on reset.
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_consumed_accumulates_and_resets() {
+        let b = BackOff::new_with_growth(10, GrowthKind::Additive(5));
+        assert_eq!(b.consumed(), 0);
+        b.wait();
+        b.wait();
+        b.wait();
+        assert_eq!(b.consumed(), 10 + 15 + 20);
+
+        b.wait_jittered(7);
+        assert!(b.consumed() > 45, "jittered rounds count what they spun");
+
+        b.reset();
+        assert_eq!(b.consumed(), 0);
+        b.wait();
+        assert_eq!(b.consumed(), u64::from(START_VALUE));
+        b.reset_to(4);
+        assert_eq!(b.consumed(), 0);
+    }
+
+    /// Ensures that the read-only previews match what `wait` then does.
+    #[cfg(all(feature = "std", feature = "metrics"))]
+    #[test]
+    fn test_peek_next_and_would_yield_match_wait() {
+        use std::time::Duration;
+
+        let backoffs = [
+            BackOff::new(),
+            BackOff::new_with_threshold(START_VALUE, START_VALUE * 2),
+            BackOff::new_with_growth(3, GrowthKind::Additive(5)),
+            BackOff::with_config(&BackOffConfig { max: 256, ..BackOffConfig::DEFAULT }),
+            BackOff::new_with_sleep(START_VALUE, 1 << 10, Duration::from_micros(1)),
+        ];
+        for b in &backoffs {
+            for round in 0..24 {
+                let (next, yields) = (b.peek_next(), b.would_yield());
+                let before = b.history();
+                b.wait();
+                let after = b.history();
+                assert_eq!(b.current(), next, "round {round}");
+                assert_eq!(after.yields - before.yields == 1, yields, "round {round}");
+            }
+        }
+    }
 }
@@ -0,0 +1,59 @@
+//! # compat
+//!
+//! Names matching the [`spin`](https://docs.rs/spin) crate's mutex, for migrating
+//! an existing code base with minimal churn.
+//!
+//! [`Mutex`] is [`SpinLock`] and [`MutexGuard`] is [`SpinGuard`], so switching is
+//! usually a one-line change of the import:
+//!
+//! ```rust
+//! // was: use spin::{Mutex, MutexGuard};
+//! use axiom_spinlock::compat::{Mutex, MutexGuard};
+//!
+//! static STATE: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+//!
+//! fn push(value: u32) {
+//!     let mut state: MutexGuard<'_, Vec<u32>> = STATE.lock();
+//!     state.push(value);
+//! }
+//!
+//! push(1);
+//! assert!(!STATE.is_locked());
+//! assert_eq!(STATE.try_lock().map(|s| s.len()), Some(1));
+//! ```
+//!
+//! ## 🔁 Method mapping
+//!
+//! | `spin::Mutex`              | `compat::Mutex`                         |
+//! |----------------------------|-----------------------------------------|
+//! | `new`, `lock`, `try_lock`  | same                                    |
+//! | `is_locked`                | same                                    |
+//! | `into_inner`, `get_mut`    | same                                    |
+//! | `MutexGuard::leak`         | same ([`SpinGuard::leak`])              |
+//! | `as_mut_ptr`               | [`SpinLock::data_ptr`]                  |
+//! | `unsafe force_unlock`      | `unsafe` [`SpinLock::unlock`]           |
+//! | `try_lock_weak`            | `try_lock` (no spurious failures)       |
+//!
+//! With the `recovery` feature there is also an `unsafe` `SpinLock::force_unlock`
+//! closer to `spin`'s: it may be called by a thread that does not hold the lock,
+//! for releasing locks stranded by dead threads, and reports whether it was held.
+//!
+//! ## ⚠️ Semantic differences
+//!
+//! - **Contention:** `spin` retries with a plain `spin_loop` hint (or a
+//!   pluggable `RelaxStrategy`); this crate escalates through an exponential
+//!   [`BackOff`](crate::BackOff) and, with `std`, eventually yields the thread.
+//!   Latency under contention differs, fairness does not improve: both locks are
+//!   **unfair**.
+//! - **No ticket lock:** `spin`'s optional FIFO `TicketMutex` has no equivalent,
+//!   so `spin::Mutex` configured with the `ticket_mutex` feature is *not* matched.
+//! - **No relax-strategy parameter:** `Mutex<T, R>` does not exist; the type has a
+//!   single parameter.
+//! - **Not reentrant**, like `spin`: relocking from the holder spins forever
+//!   (or panics with the `deadlock-detection` feature).
+
+pub use crate::spinlock::SpinGuard as MutexGuard;
+pub use crate::SpinLock as Mutex;
+
+#[cfg(doc)]
+use crate::{spinlock::SpinGuard, SpinLock};
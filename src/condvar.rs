@@ -0,0 +1,283 @@
+//! # SpinCondvar
+//!
+//! A condition variable for [`SpinLock`], built on spinning instead of OS
+//! blocking, so it works in `no_std` environments.
+//!
+//! Waiting releases the lock, spins with [`BackOff`] until a notification
+//! arrives, then reacquires the lock. Notifications are a **generation
+//! counter**: every `notify_*` call bumps it, and a waiter returns once the
+//! generation differs from the one it observed *while still holding the lock*.
+//!
+//! ## Features
+//! - ✅ `no_std` compatible (needs 32-bit atomics)
+//! - ✅ `wait`, bounded `wait_for` (spins) and, with `std`, `wait_timeout`
+//! - 🧠 A single `AtomicU32`, no waiter queue
+//!
+//! ## No lost wakeups
+//! The waiter reads the generation before it releases the lock. A notifier that
+//! changes the shared state under the same lock can only do so after that read,
+//! so its `notify_*` (under the lock or right after releasing it) always bumps
+//! the generation past the value the waiter is comparing against — including
+//! during the window between the release and the first check, and while a
+//! `wait_timeout`/`wait_for` deadline is running.
+//!
+//! ## Caveats
+//! - Spurious wakeups are possible, as with every condition variable: always
+//!   wait in a loop re-checking the condition.
+//! - There is no queue, so [`notify_one`](SpinCondvar::notify_one) may wake
+//!   several spinning waiters; prefer [`notify_all`](SpinCondvar::notify_all)
+//!   when you mean it, it costs the same.
+//! - Waiters burn CPU while waiting; keep waits short.
+//!
+//! ## Example
+//! ```rust
+//! use axiom_spinlock::{SpinCondvar, SpinLock};
+//! use std::sync::Arc;
+//! use std::thread;
+//!
+//! let pair = Arc::new((SpinLock::new(false), SpinCondvar::new()));
+//! let producer = {
+//!     let pair = pair.clone();
+//!     thread::spawn(move || {
+//!         let (ready, cvar) = &*pair;
+//!         *ready.lock() = true;
+//!         cvar.notify_one();
+//!     })
+//! };
+//!
+//! let (ready, cvar) = &*pair;
+//! let mut guard = ready.lock();
+//! while !*guard {
+//!     guard = cvar.wait(guard);
+//! }
+//! producer.join().unwrap();
+//! ```
+
+use core::sync::atomic::{
+    AtomicU32,
+    Ordering::{Acquire, Relaxed, Release},
+};
+
+use crate::spinlock::SpinGuard;
+use crate::BackOff;
+
+#[cfg(doc)]
+use crate::SpinLock;
+
+/// A spin-based condition variable for use with [`SpinLock`].
+///
+/// See the [module-level documentation](crate::condvar) for the wakeup protocol.
+pub struct SpinCondvar {
+    generation: AtomicU32,
+}
+
+/// Whether a bounded wait on a [`SpinCondvar`] ended because its budget ran out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WaitTimeoutResult(bool);
+
+impl WaitTimeoutResult {
+    /// Returns `true` if the wait ended without a notification.
+    #[inline(always)]
+    pub fn timed_out(&self) -> bool {
+        self.0
+    }
+}
+
+impl SpinCondvar {
+    /// Creates a new [`SpinCondvar`].
+    #[inline(always)]
+    pub const fn new() -> Self {
+        SpinCondvar {
+            generation: AtomicU32::new(0),
+        }
+    }
+
+    /// Releases the lock, waits for a notification and reacquires the lock.
+    ///
+    /// May return spuriously; call it in a loop that re-checks the condition.
+    #[inline]
+    pub fn wait<'a, T>(&self, guard: SpinGuard<'a, T>) -> SpinGuard<'a, T> {
+        let seen = self.generation.load(Acquire);
+        let lock = SpinGuard::unlocked(guard);
+        let backoff = BackOff::new();
+        while self.generation.load(Acquire) == seen {
+            backoff.wait();
+        }
+        lock.lock()
+    }
+
+    /// Like [`wait`](Self::wait), but gives up after `spins` backoff rounds.
+    ///
+    /// Works in `no_std`. The lock is reacquired in either case; the
+    /// [`WaitTimeoutResult`] tells whether a notification arrived.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::{SpinCondvar, SpinLock};
+    ///
+    /// let lock = SpinLock::new(0);
+    /// let cvar = SpinCondvar::new();
+    /// let (guard, result) = cvar.wait_for(lock.lock(), 16);
+    /// assert!(result.timed_out(), "nobody notified");
+    /// assert_eq!(*guard, 0);
+    /// ```
+    #[inline]
+    pub fn wait_for<'a, T>(
+        &self,
+        guard: SpinGuard<'a, T>,
+        spins: usize,
+    ) -> (SpinGuard<'a, T>, WaitTimeoutResult) {
+        let seen = self.generation.load(Acquire);
+        let lock = SpinGuard::unlocked(guard);
+        let backoff = BackOff::new();
+        let mut notified = false;
+        for _ in 0..spins {
+            if self.generation.load(Acquire) != seen {
+                notified = true;
+                break;
+            }
+            backoff.wait();
+        }
+        (lock.lock(), WaitTimeoutResult(!notified))
+    }
+
+    /// Like [`wait`](Self::wait), but gives up once `dur` has elapsed (only
+    /// available with `std`).
+    ///
+    /// The lock is reacquired in either case; reacquiring may take longer than
+    /// `dur` if the lock is contended.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn wait_timeout<'a, T>(
+        &self,
+        guard: SpinGuard<'a, T>,
+        dur: core::time::Duration,
+    ) -> (SpinGuard<'a, T>, WaitTimeoutResult) {
+        let start = std::time::Instant::now();
+        let seen = self.generation.load(Acquire);
+        let lock = SpinGuard::unlocked(guard);
+        let backoff = BackOff::new();
+        let notified = loop {
+            if self.generation.load(Acquire) != seen {
+                break true;
+            }
+            if start.elapsed() >= dur {
+                break false;
+            }
+            backoff.wait();
+        };
+        (lock.lock(), WaitTimeoutResult(!notified))
+    }
+
+    /// Wakes up waiters.
+    ///
+    /// Without a waiter queue this is the same as [`notify_all`](Self::notify_all):
+    /// every thread currently waiting may return.
+    #[inline]
+    pub fn notify_one(&self) {
+        self.notify_all();
+    }
+
+    /// Wakes up all waiters.
+    #[inline]
+    pub fn notify_all(&self) {
+        self.generation.fetch_add(1, Release);
+    }
+
+    /// Returns the number of notifications so far (wrapping), for diagnostics.
+    #[inline(always)]
+    pub fn generation(&self) -> u32 {
+        self.generation.load(Relaxed)
+    }
+}
+
+impl Default for SpinCondvar {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_wait_wakes_on_notify() {
+        use crate::{SpinCondvar, SpinLock};
+        use std::sync::Arc;
+        use std::thread;
+
+        let pair = Arc::new((SpinLock::new(Vec::new()), SpinCondvar::new()));
+        let consumer = {
+            let pair = pair.clone();
+            thread::spawn(move || {
+                let (queue, cvar) = &*pair;
+                let mut guard = queue.lock();
+                while guard.len() < 3 {
+                    guard = cvar.wait(guard);
+                }
+                guard.iter().sum::<u32>()
+            })
+        };
+
+        let (queue, cvar) = &*pair;
+        for item in 1..=3 {
+            queue.lock().push(item);
+            cvar.notify_one();
+        }
+        assert_eq!(consumer.join().unwrap(), 6);
+        assert_eq!(cvar.generation(), 3);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_wait_timeout_elapses_without_notification() {
+        use crate::{SpinCondvar, SpinLock};
+        use std::time::{Duration, Instant};
+
+        let lock = SpinLock::new(7);
+        let cvar = SpinCondvar::new();
+
+        let start = Instant::now();
+        let (guard, result) = cvar.wait_timeout(lock.lock(), Duration::from_millis(20));
+        assert!(result.timed_out());
+        assert!(start.elapsed() >= Duration::from_millis(20));
+        assert_eq!(*guard, 7, "Lock should be reacquired after a timeout");
+        assert!(lock.is_locked());
+        drop(guard);
+
+        let (_guard, result) = cvar.wait_for(lock.lock(), 8);
+        assert!(result.timed_out());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_wait_timeout_reports_notification() {
+        use crate::{SpinCondvar, SpinLock};
+        use std::sync::Arc;
+        use std::thread;
+        use std::time::Duration;
+
+        let pair = Arc::new((SpinLock::new(false), SpinCondvar::new()));
+        let (flag, cvar) = &*pair;
+        let mut guard = flag.lock();
+
+        let notifier = {
+            let pair = pair.clone();
+            thread::spawn(move || {
+                let (flag, cvar) = &*pair;
+                *flag.lock() = true;
+                cvar.notify_all();
+            })
+        };
+
+        // The notification may land before the wait starts: no wakeup is lost.
+        while !*guard {
+            let (next, result) = cvar.wait_timeout(guard, Duration::from_secs(10));
+            assert!(!result.timed_out(), "Notification was lost");
+            guard = next;
+        }
+        drop(guard);
+        notifier.join().unwrap();
+    }
+}
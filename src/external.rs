@@ -0,0 +1,170 @@
+//! # ExternalSpinLock
+//!
+//! Spinlocks whose flag and data live in **separate** places, for
+//! struct-of-arrays layouts: all lock flags in one dense array (64 locks per
+//! cache line, cheap to scan), all protected values in another.
+//!
+//! [`ExternalSpinLock::lock`] pairs a caller-provided [`AtomicBool`] with an
+//! [`UnsafeCell<T>`] for the duration of one [`ExternalSpinGuard`]. The flag is
+//! driven through [`RawSpinLock::from_atomic`], so it follows exactly the same
+//! protocol (and [`BackOff`](crate::BackOff) contention behavior) as
+//! [`SpinLock`](crate::SpinLock).
+//!
+//! ## Soundness contract
+//!
+//! Nothing ties a flag to its data at the type level, so acquisition is
+//! `unsafe`. For every data cell, the caller must ensure that:
+//!
+//! - 🔗 **one flag per cell:** every access to the cell, from every thread, goes
+//!   through a guard obtained with the *same* flag (one flag may guard several
+//!   cells, but a cell must never be reachable through two flags);
+//! - 🚫 **no bypass:** the cell is not otherwise read or written while any guard
+//!   for it may be alive, and the flag is not stored to outside this protocol;
+//! - 🧵 **thread safety:** sharing the cells across threads is only sound for
+//!   `T: Send`, as with `SpinLock<T>`; the container that makes the cells
+//!   `Sync` is the caller's, and so is that obligation.
+//!
+//! ## Example
+//! ```rust
+//! use axiom_spinlock::ExternalSpinLock;
+//! use core::cell::UnsafeCell;
+//! use core::sync::atomic::AtomicBool;
+//!
+//! let flags = [const { AtomicBool::new(false) }; 4];
+//! let values = [const { UnsafeCell::new(0u32) }; 4];
+//!
+//! // Safety: entry `i` of `values` is only ever accessed under `flags[i]`.
+//! let mut third = unsafe { ExternalSpinLock::lock(&flags[2], &values[2]) };
+//! *third += 1;
+//! drop(third);
+//! ```
+
+use core::cell::UnsafeCell;
+use core::ops::{Deref, DerefMut};
+use core::sync::atomic::AtomicBool;
+
+use crate::RawSpinLock;
+
+/// Entry points for locking data guarded by an external flag.
+///
+/// See the [module-level documentation](crate::external) for the soundness
+/// contract shared by every function here.
+pub struct ExternalSpinLock;
+
+/// Exclusive access to an external lock's data, released on drop.
+pub struct ExternalSpinGuard<'a, T> {
+    lock: &'a RawSpinLock,
+    data: &'a UnsafeCell<T>,
+}
+
+impl ExternalSpinLock {
+    /// Acquires `flag`, spinning with backoff, and returns a guard for `data`.
+    ///
+    /// # Safety
+    /// `data` must only ever be accessed under `flag`, as described in the
+    /// [soundness contract](crate::external#soundness-contract).
+    #[inline]
+    pub unsafe fn lock<'a, T>(
+        flag: &'a AtomicBool,
+        data: &'a UnsafeCell<T>,
+    ) -> ExternalSpinGuard<'a, T> {
+        let lock = RawSpinLock::from_atomic(flag);
+        lock.lock();
+        ExternalSpinGuard { lock, data }
+    }
+
+    /// Attempts to acquire `flag` once, returning a guard for `data` on success.
+    ///
+    /// # Safety
+    /// Same contract as [`lock`](Self::lock).
+    #[inline]
+    pub unsafe fn try_lock<'a, T>(
+        flag: &'a AtomicBool,
+        data: &'a UnsafeCell<T>,
+    ) -> Option<ExternalSpinGuard<'a, T>> {
+        let lock = RawSpinLock::from_atomic(flag);
+        if lock.try_lock() {
+            Some(ExternalSpinGuard { lock, data })
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Deref for ExternalSpinGuard<'_, T> {
+    type Target = T;
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        // Safety: the flag is held and, by the contract, guards this cell.
+        unsafe { &*self.data.get() }
+    }
+}
+
+impl<T> DerefMut for ExternalSpinGuard<'_, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        // Safety: the flag is held and, by the contract, guards this cell.
+        unsafe { &mut *self.data.get() }
+    }
+}
+
+impl<T> Drop for ExternalSpinGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        // Safety: this guard acquired the flag and has not released it.
+        unsafe { self.lock.unlock() };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_flags_sharing_a_cache_line() {
+        use crate::ExternalSpinLock;
+        use core::cell::UnsafeCell;
+        use core::sync::atomic::AtomicBool;
+        use std::thread;
+
+        const ENTRIES: usize = 8;
+
+        #[repr(align(64))]
+        struct Flags([AtomicBool; ENTRIES]);
+
+        struct Values([UnsafeCell<u64>; ENTRIES]);
+        // Safety: each cell is only accessed under its own flag.
+        unsafe impl Sync for Values {}
+
+        let flags = Flags([const { AtomicBool::new(false) }; ENTRIES]);
+        let values = Values([const { UnsafeCell::new(0) }; ENTRIES]);
+        let first = &flags.0[0] as *const AtomicBool as usize;
+        let last = &flags.0[ENTRIES - 1] as *const AtomicBool as usize;
+        assert_eq!(first / 64, last / 64, "all flags share one cache line");
+
+        thread::scope(|s| {
+            for t in 0..4 {
+                let (flags, values) = (&flags, &values);
+                s.spawn(move || {
+                    for n in 0..2_000 {
+                        // Every thread touches every entry, neighbours back to back.
+                        let i = (t + n) % ENTRIES;
+                        let mut v = unsafe { ExternalSpinLock::lock(&flags.0[i], &values.0[i]) };
+                        *v += 1;
+                    }
+                });
+            }
+        });
+
+        let total: u64 = values.0.iter().map(|v| unsafe { *v.get() }).sum();
+        assert_eq!(total, 8_000);
+        assert!(flags
+            .0
+            .iter()
+            .all(|f| !f.load(core::sync::atomic::Ordering::Relaxed)));
+
+        let held = unsafe { ExternalSpinLock::lock(&flags.0[3], &values.0[3]) };
+        assert!(unsafe { ExternalSpinLock::try_lock(&flags.0[3], &values.0[3]) }.is_none());
+        assert!(unsafe { ExternalSpinLock::try_lock(&flags.0[4], &values.0[4]) }.is_some());
+        drop(held);
+    }
+}
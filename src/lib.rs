@@ -66,15 +66,143 @@
 //!
 //! - [`backoff`] — Adaptive exponential backoff mechanism.  
 //! - [`spinlock`] — Spin-based synchronization primitive.  
+//! - [`raw`] — The data-less lock underlying `SpinLock`.  
+//! - [`word`] — `SpinLock` variants backed by wider atomic flags.  
+//! - [`rwlock`] — Reader-writer spinlock with upgradable reads.  
+//! - [`condvar`] — Spin-based condition variable for `SpinLock`.  
+//! - [`external`] — Locks whose flag lives apart from the data (struct-of-arrays).  
+//! - [`packed`] — Integer locks packing the flag into the value's top bit.  
+//! - [`compat`] — `spin`-style `Mutex`/`MutexGuard` names for easy migration.  
+//!
+//! ## ⚛️ Atomic Requirements
+//!
+//! The lock flags need read-modify-write (`swap`) atomics from the target:
+//!
+//! - [`SpinLock`], [`RawSpinLock`] and [`ExternalSpinLock`] require 8-bit atomics (`target_has_atomic = "8"`);
+//!   [`SpinLock::new_adaptive`] additionally needs 32-bit atomics for its retuned backoff start.
+//! - [`WordSpinLock`] requires pointer-width atomics (`target_has_atomic = "ptr"`).
+//! - [`SpinLockU32`] requires 32-bit atomics (`target_has_atomic = "32"`).
+//! - [`SpinCondvar`] requires both 8-bit and 32-bit atomics.
+//! - [`RwSpinLock`] requires pointer-width atomics (`target_has_atomic = "ptr"`).
+//! - [`PackedSpinLock`] requires 32-bit atomics for `u32` and 64-bit atomics for `u64` payloads.
+//! - [`BackOff`] needs no atomics at all.
+//!
+//! Each lock type is only compiled when its requirement is met, so on targets
+//! with word atomics but no byte atomics you can use [`WordSpinLock`] as a
+//! drop-in replacement. If the target has neither, the crate refuses to build
+//! with an explanatory error.
+//!
+//! Feature combinations that cannot work on the target are rejected the same
+//! way: `adaptive`, `contention-hint`, `parking`, `deadlock-detection`, `fenced-acquire`, `profiling`,
+//! `recovery` and `lock_api` require 8-bit atomics, `profiling` also needs pointer-width atomics,
+//! and `wasm-atomics` on `wasm32` requires a nightly build with `-C target-feature=+atomics`.
 //!
 //!
 //! ### Crate Exports
 //!
 //! - [`BackOff`] — from [`backoff`]  
 //! - [`SpinLock`] — from [`spinlock`]
+//! - [`SpinCondvar`] — from [`condvar`]
+//! - [`RawSpinLock`] — from [`raw`] (plus `SpinMutex` with the `lock_api` feature)
+//! - [`WordSpinLock`], [`SpinLockU32`] — from [`word`]
+//! - [`RwSpinLock`] — from [`rwlock`]
+//! - [`ExternalSpinLock`] — from [`external`]
+//! - [`PackedSpinLock`] — from [`packed`]
+//! - `profiling::report` — from `profiling` (with the `profiling` feature)
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+// The wasm wait/notify intrinsics are still unstable: `wasm-atomics` on wasm
+// threads needs a nightly toolchain.
+#![cfg_attr(
+    all(feature = "wasm-atomics", target_arch = "wasm32", target_feature = "atomics"),
+    feature(stdarch_wasm_atomic_wait)
+)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+// Build-time validation of the target and feature matrix: every combination
+// that cannot work fails here with an actionable message, instead of surfacing
+// as missing items or type errors deep inside the crate. Checks for new
+// features belong in this block.
+
+#[cfg(not(any(target_has_atomic = "8", target_has_atomic = "ptr")))]
+compile_error!(
+    "axiom-spinlock requires atomic swap support: the target must provide either \
+     8-bit atomics (for `SpinLock`) or pointer-width atomics (for `WordSpinLock`)."
+);
+
+#[cfg(all(
+    not(target_has_atomic = "8"),
+    any(
+        feature = "adaptive",
+        feature = "contention-hint",
+        feature = "parking",
+        feature = "deadlock-detection",
+        feature = "fenced-acquire",
+        feature = "profiling",
+        feature = "recovery",
+        feature = "lock_api"
+    )
+))]
+compile_error!(
+    "the `adaptive`, `contention-hint`, `parking`, `deadlock-detection`, `fenced-acquire`, `profiling`, `recovery` and `lock_api` features extend `SpinLock`/`RawSpinLock`, \
+     which need 8-bit atomics that this target lacks; disable them (and use `WordSpinLock`)."
+);
+
+#[cfg(all(feature = "profiling", not(target_has_atomic = "ptr")))]
+compile_error!(
+    "the `profiling` feature links its registry with pointer-width atomics, which this target lacks; disable it."
+);
+
+#[cfg(all(
+    feature = "wasm-atomics",
+    target_arch = "wasm32",
+    not(target_feature = "atomics")
+))]
+compile_error!(
+    "the `wasm-atomics` feature needs wasm threads, which only a nightly toolchain enables: \
+     build with `cargo +nightly build -Zbuild-std=std,panic_abort` and \
+     RUSTFLAGS=\"-C target-feature=+atomics,+bulk-memory\" (on stable these flags are ignored \
+     and this error persists; see the `word` module docs), or disable the feature to spin without blocking."
+);
 
 pub mod backoff;
+#[cfg(target_has_atomic = "8")]
+pub mod compat;
+#[cfg(all(target_has_atomic = "8", target_has_atomic = "32"))]
+pub mod condvar;
+#[cfg(target_has_atomic = "8")]
+pub mod external;
+#[cfg(any(target_has_atomic = "32", target_has_atomic = "64"))]
+pub mod packed;
+#[cfg(all(feature = "profiling", target_has_atomic = "8"))]
+pub mod profiling;
+#[cfg(target_has_atomic = "8")]
+pub mod raw;
+#[cfg(target_has_atomic = "ptr")]
+pub mod rwlock;
+#[cfg(target_has_atomic = "8")]
 pub mod spinlock;
+#[cfg(any(target_has_atomic = "32", target_has_atomic = "ptr"))]
+pub mod word;
 
 pub use backoff::BackOff;
+#[cfg(all(target_has_atomic = "8", target_has_atomic = "32"))]
+pub use condvar::SpinCondvar;
+#[cfg(target_has_atomic = "8")]
+pub use external::ExternalSpinLock;
+#[cfg(any(target_has_atomic = "32", target_has_atomic = "64"))]
+pub use packed::PackedSpinLock;
+#[cfg(target_has_atomic = "8")]
+pub use raw::RawSpinLock;
+#[cfg(all(feature = "lock_api", target_has_atomic = "8"))]
+pub use raw::SpinMutex;
+#[cfg(target_has_atomic = "ptr")]
+pub use rwlock::RwSpinLock;
+#[cfg(target_has_atomic = "8")]
 pub use spinlock::SpinLock;
+#[cfg(target_has_atomic = "32")]
+pub use word::SpinLockU32;
+#[cfg(target_has_atomic = "ptr")]
+pub use word::WordSpinLock;
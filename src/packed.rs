@@ -0,0 +1,377 @@
+//! # Packed spinlocks
+//!
+//! [`PackedSpinLock`] stores a small integer and its lock flag in **one**
+//! atomic word: the top bit is the flag, the remaining bits are the value.
+//!
+//! - 📦 **No extra bytes:** a `PackedSpinLock<u32>` is exactly 4 bytes (a
+//!   `SpinLock<u32>` is at least 8), so arrays of tiny locked counters stay
+//!   dense — 16 locks per 64-byte cache line.
+//! - ⚛️ **One word, one line:** flag and value can never straddle cache lines,
+//!   and the release that publishes a new value is the same store that clears
+//!   the flag.
+//! - 📏 **Enforced range:** values must fit below the flag bit, up to
+//!   [`PackedSpinLock::<u32>::MAX`](PackedSpinLock#associatedconstant.MAX)
+//!   (`u32::MAX >> 1`) or `u64::MAX >> 1`. Constructors and
+//!   [`PackedSpinGuard::set`] panic on anything larger, so a value can never
+//!   leak into the flag.
+//!
+//! The guard works on a copy of the value read when the lock was taken and
+//! writes it back on drop, in the `Release` store that unlocks. It only hands
+//! out `&T`; change the value with [`set`](PackedSpinGuard::set).
+//!
+//! Implemented for `u32` (needs `target_has_atomic = "32"`) and `u64` (needs
+//! `target_has_atomic = "64"`).
+//!
+//! ## Example
+//! ```rust
+//! use axiom_spinlock::PackedSpinLock;
+//!
+//! static HITS: [PackedSpinLock<u32>; 4] = [const { PackedSpinLock::<u32>::new(0) }; 4];
+//!
+//! let mut slot = HITS[2].lock();
+//! slot.set(*slot + 1);
+//! drop(slot);
+//! assert_eq!(*HITS[2].lock(), 1);
+//! assert_eq!(core::mem::size_of_val(&HITS), 16);
+//! ```
+
+use core::ops::Deref;
+#[cfg(target_has_atomic = "32")]
+use core::sync::atomic::AtomicU32;
+#[cfg(target_has_atomic = "64")]
+use core::sync::atomic::AtomicU64;
+use core::sync::atomic::Ordering::{Acquire, Relaxed, Release};
+
+use crate::BackOff;
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// An integer type that can share its atomic word with a lock flag.
+///
+/// This trait is sealed; it is implemented for `u32` and `u64` on targets with
+/// atomics of that width.
+pub trait PackedInt: private::Sealed + Copy {
+    /// The atomic word holding the flag and the value.
+    #[doc(hidden)]
+    type Atomic;
+
+    /// Sets the flag if it was clear, returning the value it guarded.
+    #[doc(hidden)]
+    fn try_acquire(word: &Self::Atomic) -> Option<Self>;
+
+    /// Stores `value` with the flag clear, with `Release` ordering.
+    #[doc(hidden)]
+    fn release(word: &Self::Atomic, value: Self);
+
+    /// Returns whether the flag is set, with `Relaxed` ordering.
+    #[doc(hidden)]
+    fn is_held(word: &Self::Atomic) -> bool;
+
+    /// Unwraps the value of an unlocked word.
+    #[doc(hidden)]
+    fn into_value(word: Self::Atomic) -> Self;
+
+    /// Returns whether `value` leaves the flag bit clear.
+    #[doc(hidden)]
+    fn fits(value: Self) -> bool;
+}
+
+#[cfg(target_has_atomic = "32")]
+impl private::Sealed for u32 {}
+
+#[cfg(target_has_atomic = "32")]
+impl PackedInt for u32 {
+    type Atomic = AtomicU32;
+
+    #[inline(always)]
+    fn try_acquire(word: &AtomicU32) -> Option<u32> {
+        let previous = word.fetch_or(PackedSpinLock::<u32>::LOCK_BIT, Acquire);
+        (previous & PackedSpinLock::<u32>::LOCK_BIT == 0).then_some(previous)
+    }
+
+    #[inline(always)]
+    fn release(word: &AtomicU32, value: u32) {
+        word.store(value, Release);
+    }
+
+    #[inline(always)]
+    fn is_held(word: &AtomicU32) -> bool {
+        word.load(Relaxed) & PackedSpinLock::<u32>::LOCK_BIT != 0
+    }
+
+    #[inline(always)]
+    fn into_value(word: AtomicU32) -> u32 {
+        word.into_inner()
+    }
+
+    #[inline(always)]
+    fn fits(value: u32) -> bool {
+        value <= PackedSpinLock::<u32>::MAX
+    }
+}
+
+#[cfg(target_has_atomic = "64")]
+impl private::Sealed for u64 {}
+
+#[cfg(target_has_atomic = "64")]
+impl PackedInt for u64 {
+    type Atomic = AtomicU64;
+
+    #[inline(always)]
+    fn try_acquire(word: &AtomicU64) -> Option<u64> {
+        let previous = word.fetch_or(PackedSpinLock::<u64>::LOCK_BIT, Acquire);
+        (previous & PackedSpinLock::<u64>::LOCK_BIT == 0).then_some(previous)
+    }
+
+    #[inline(always)]
+    fn release(word: &AtomicU64, value: u64) {
+        word.store(value, Release);
+    }
+
+    #[inline(always)]
+    fn is_held(word: &AtomicU64) -> bool {
+        word.load(Relaxed) & PackedSpinLock::<u64>::LOCK_BIT != 0
+    }
+
+    #[inline(always)]
+    fn into_value(word: AtomicU64) -> u64 {
+        word.into_inner()
+    }
+
+    #[inline(always)]
+    fn fits(value: u64) -> bool {
+        value <= PackedSpinLock::<u64>::MAX
+    }
+}
+
+/// A spinlock around a small integer, packed with its flag into one atomic word.
+///
+/// See the [module-level documentation](crate::packed) for the layout and the
+/// range of storable values.
+pub struct PackedSpinLock<T: PackedInt> {
+    word: T::Atomic,
+}
+
+/// A guard that writes its value back and releases the [`PackedSpinLock`] when
+/// dropped.
+///
+/// Derefs to the value read when the lock was taken; [`set`](Self::set)
+/// replaces it.
+pub struct PackedSpinGuard<'a, T: PackedInt> {
+    lock: &'a PackedSpinLock<T>,
+    value: T,
+}
+
+#[cfg(target_has_atomic = "32")]
+impl PackedSpinLock<u32> {
+    /// The flag: the top bit of the word.
+    const LOCK_BIT: u32 = 1 << 31;
+
+    /// The largest storable value, `u32::MAX >> 1`.
+    pub const MAX: u32 = u32::MAX >> 1;
+
+    /// Creates a new, unlocked [`PackedSpinLock`] holding `value`.
+    ///
+    /// # Panics
+    /// If `value` exceeds [`MAX`](Self::MAX) (at compile time in const contexts).
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::PackedSpinLock;
+    ///
+    /// let lock = PackedSpinLock::<u32>::new(PackedSpinLock::<u32>::MAX);
+    /// assert_eq!(*lock.lock(), u32::MAX >> 1);
+    /// ```
+    #[inline(always)]
+    pub const fn new(value: u32) -> Self {
+        assert!(value <= Self::MAX, "value does not fit below the lock bit");
+        PackedSpinLock {
+            word: AtomicU32::new(value),
+        }
+    }
+}
+
+#[cfg(target_has_atomic = "64")]
+impl PackedSpinLock<u64> {
+    /// The flag: the top bit of the word.
+    const LOCK_BIT: u64 = 1 << 63;
+
+    /// The largest storable value, `u64::MAX >> 1`.
+    pub const MAX: u64 = u64::MAX >> 1;
+
+    /// Creates a new, unlocked [`PackedSpinLock`] holding `value`.
+    ///
+    /// # Panics
+    /// If `value` exceeds [`MAX`](Self::MAX) (at compile time in const contexts).
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::PackedSpinLock;
+    ///
+    /// let lock = PackedSpinLock::<u64>::new(1 << 40);
+    /// assert_eq!(*lock.lock(), 1 << 40);
+    /// ```
+    #[inline(always)]
+    pub const fn new(value: u64) -> Self {
+        assert!(value <= Self::MAX, "value does not fit below the lock bit");
+        PackedSpinLock {
+            word: AtomicU64::new(value),
+        }
+    }
+}
+
+impl<T: PackedInt> PackedSpinLock<T> {
+    /// Acquires the lock, spinning with an exponential [`BackOff`] until it
+    /// becomes available.
+    #[inline]
+    pub fn lock(&self) -> PackedSpinGuard<'_, T> {
+        let backoff = BackOff::new();
+        loop {
+            if let Some(value) = T::try_acquire(&self.word) {
+                return PackedSpinGuard { lock: self, value };
+            }
+            while T::is_held(&self.word) {
+                backoff.wait();
+            }
+        }
+    }
+
+    /// Attempts to acquire the lock without blocking.
+    #[inline]
+    pub fn try_lock(&self) -> Option<PackedSpinGuard<'_, T>> {
+        T::try_acquire(&self.word).map(|value| PackedSpinGuard { lock: self, value })
+    }
+
+    /// Checks whether the lock is currently held (a racy snapshot).
+    #[inline(always)]
+    pub fn is_locked(&self) -> bool {
+        T::is_held(&self.word)
+    }
+
+    /// Consumes the lock and returns the value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        T::into_value(self.word)
+    }
+}
+
+impl<T: PackedInt> PackedSpinGuard<'_, T> {
+    /// Replaces the value, which is written back when the guard is dropped.
+    ///
+    /// # Panics
+    /// If `value` does not fit below the flag bit (above
+    /// [`PackedSpinLock::<u32>::MAX`](PackedSpinLock#associatedconstant.MAX) or
+    /// its `u64` counterpart).
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::PackedSpinLock;
+    ///
+    /// let lock = PackedSpinLock::<u32>::new(1);
+    /// lock.lock().set(7);
+    /// assert_eq!(*lock.lock(), 7);
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn set(&mut self, value: T) {
+        assert!(T::fits(value), "value does not fit below the lock bit");
+        self.value = value;
+    }
+}
+
+impl<T: PackedInt> Deref for PackedSpinGuard<'_, T> {
+    type Target = T;
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: PackedInt> Drop for PackedSpinGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        T::release(&self.lock.word, self.value);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(target_has_atomic = "32")]
+    #[test]
+    fn test_bit_packing_u32() {
+        use crate::PackedSpinLock;
+        use core::sync::atomic::Ordering::Relaxed;
+
+        let lock = PackedSpinLock::<u32>::new(0x1234_5678);
+        assert_eq!(core::mem::size_of_val(&lock), 4);
+        assert_eq!(lock.word.load(Relaxed), 0x1234_5678);
+
+        let mut guard = lock.lock();
+        assert_eq!(lock.word.load(Relaxed), 0x9234_5678, "flag in the top bit");
+        assert!(lock.try_lock().is_none());
+        assert_eq!(
+            lock.word.load(Relaxed),
+            0x9234_5678,
+            "failed try_lock keeps the value"
+        );
+        guard.set(PackedSpinLock::<u32>::MAX);
+        drop(guard);
+
+        assert_eq!(
+            lock.word.load(Relaxed),
+            u32::MAX >> 1,
+            "value stored, flag clear"
+        );
+        assert!(!lock.is_locked());
+        assert_eq!(lock.into_inner(), u32::MAX >> 1);
+    }
+
+    #[cfg(target_has_atomic = "64")]
+    #[test]
+    fn test_bit_packing_u64() {
+        use crate::PackedSpinLock;
+        use core::sync::atomic::Ordering::Relaxed;
+
+        let lock = PackedSpinLock::<u64>::new(1 << 62);
+        assert_eq!(core::mem::size_of_val(&lock), 8);
+        let guard = lock.lock();
+        assert_eq!(lock.word.load(Relaxed), (1 << 63) | (1 << 62));
+        assert_eq!(*guard, 1 << 62);
+        drop(guard);
+        assert_eq!(lock.word.load(Relaxed), 1 << 62);
+    }
+
+    #[cfg(target_has_atomic = "32")]
+    #[test]
+    #[should_panic(expected = "does not fit below the lock bit")]
+    fn test_set_rejects_lock_bit() {
+        use crate::PackedSpinLock;
+
+        let lock = PackedSpinLock::<u32>::new(0);
+        lock.lock().set(1 << 31);
+    }
+
+    #[cfg(all(feature = "std", target_has_atomic = "32"))]
+    #[test]
+    fn test_concurrent_packed_counters() {
+        use crate::PackedSpinLock;
+        use std::thread;
+
+        let counters = [const { PackedSpinLock::<u32>::new(0) }; 4];
+        thread::scope(|s| {
+            for t in 0..4 {
+                let counters = &counters;
+                s.spawn(move || {
+                    for i in 0..2_000 {
+                        let mut slot = counters[(t + i) % 4].lock();
+                        slot.set(*slot + 1);
+                    }
+                });
+            }
+        });
+        let total: u32 = counters.into_iter().map(PackedSpinLock::into_inner).sum();
+        assert_eq!(total, 8_000);
+    }
+}
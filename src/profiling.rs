@@ -0,0 +1,216 @@
+//! # Profiling
+//!
+//! A program-wide registry of lock contention, enabled by the **`profiling`**
+//! feature (implies `alloc`).
+//!
+//! Locks created with [`SpinLock::with_name`](crate::SpinLock::with_name) join
+//! the *site* of that name; every [`SpinLock::lock`](crate::SpinLock::lock) on
+//! them adds to the site's counters. [`report`] snapshots all sites, hottest
+//! first, so you can see which locks a program fights over without external
+//! tooling. Locks created any other way are not tracked and pay nothing.
+//!
+//! ## Design
+//! - 🔗 **Lock-free list:** sites are nodes of an intrusive singly linked list,
+//!   pushed with a compare-and-swap on a global head. Registering never blocks
+//!   and never takes a lock, so the registry cannot deadlock against the locks
+//!   it profiles.
+//! - 📊 **Relaxed counters:** a `lock()` on a named lock does two or three
+//!   relaxed `fetch_add`s on its site, after the lock is already held. Counts
+//!   are statistics: a report taken while locks are in use may be slightly
+//!   behind, never torn per counter.
+//! - ♾️ **Leaked sites:** a site lives for the rest of the program, so counters
+//!   survive the locks that fed them; memory is bounded by the number of
+//!   distinct names, which should be small and static.
+//!
+//! Locks sharing a name aggregate into one site, e.g. all shards of a sharded
+//! map under `"cache.shard"`.
+//!
+//! ## Example
+//! ```rust
+//! use axiom_spinlock::{profiling, SpinLock};
+//!
+//! let queue = SpinLock::with_name("doc.queue", Vec::new());
+//! queue.lock().push(1);
+//! queue.lock().push(2);
+//!
+//! let stats = profiling::report();
+//! let site = stats.iter().find(|s| s.name == "doc.queue").unwrap();
+//! assert_eq!(site.acquisitions, 2);
+//! ```
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::ptr;
+use core::sync::atomic::{
+    AtomicPtr, AtomicUsize,
+    Ordering::{AcqRel, Acquire, Relaxed},
+};
+
+/// Head of the list of every registered site.
+static SITES: AtomicPtr<LockSite> = AtomicPtr::new(ptr::null_mut());
+
+/// The shared counters of all locks registered under one name.
+pub struct LockSite {
+    name: &'static str,
+    acquisitions: AtomicUsize,
+    contended: AtomicUsize,
+    spin_rounds: AtomicUsize,
+    next: *const LockSite,
+}
+
+// Safety: `next` is written once before the node is published and only read
+// afterwards; everything else is atomic or immutable.
+unsafe impl Sync for LockSite {}
+
+impl LockSite {
+    /// Returns the site registered under `name`, registering it first if needed.
+    pub(crate) fn register(name: &'static str) -> &'static LockSite {
+        let mut head = SITES.load(Acquire);
+        if let Some(site) = find(head, ptr::null(), name) {
+            return site;
+        }
+        let node = Box::into_raw(Box::new(LockSite {
+            name,
+            acquisitions: AtomicUsize::new(0),
+            contended: AtomicUsize::new(0),
+            spin_rounds: AtomicUsize::new(0),
+            next: head,
+        }));
+        loop {
+            match SITES.compare_exchange_weak(head, node, AcqRel, Acquire) {
+                // Safety: published nodes are never freed.
+                Ok(_) => return unsafe { &*node },
+                Err(current) => {
+                    // Someone registered concurrently; it may be this very name.
+                    if let Some(site) = find(current, head, name) {
+                        // Safety: `node` was never published.
+                        drop(unsafe { Box::from_raw(node) });
+                        return site;
+                    }
+                    head = current;
+                    // Safety: `node` is still private to this thread.
+                    unsafe { (*node).next = head };
+                }
+            }
+        }
+    }
+
+    /// Adds one `lock()` that took `rounds` backoff rounds.
+    #[inline]
+    pub(crate) fn record(&self, rounds: u32) {
+        self.acquisitions.fetch_add(1, Relaxed);
+        if rounds > 0 {
+            self.contended.fetch_add(1, Relaxed);
+            self.spin_rounds.fetch_add(rounds as usize, Relaxed);
+        }
+    }
+
+    fn stats(&self) -> LockSiteStats {
+        LockSiteStats {
+            name: self.name,
+            acquisitions: self.acquisitions.load(Relaxed) as u64,
+            contended: self.contended.load(Relaxed) as u64,
+            spin_rounds: self.spin_rounds.load(Relaxed) as u64,
+        }
+    }
+}
+
+/// Searches the nodes from `from` up to (excluding) `until` for `name`.
+fn find(from: *const LockSite, until: *const LockSite, name: &str) -> Option<&'static LockSite> {
+    let mut cursor = from;
+    while !cursor.is_null() && cursor != until {
+        // Safety: published nodes are never freed or mutated.
+        let site = unsafe { &*cursor };
+        if site.name == name {
+            return Some(site);
+        }
+        cursor = site.next;
+    }
+    None
+}
+
+/// Contention counters of one lock site, returned by [`report`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockSiteStats {
+    /// The name given to [`SpinLock::with_name`](crate::SpinLock::with_name).
+    pub name: &'static str,
+    /// `lock()` calls on the site's locks.
+    pub acquisitions: u64,
+    /// Of those, the ones that found the lock held and had to back off.
+    pub contended: u64,
+    /// Backoff rounds spent waiting, summed over all contended calls.
+    pub spin_rounds: u64,
+}
+
+/// Snapshots every registered site, most contended first.
+///
+/// Reads only relaxed counters and never locks anything, so it is safe to call
+/// at any time, even while holding a profiled lock.
+pub fn report() -> Vec<LockSiteStats> {
+    let mut stats = Vec::new();
+    let mut cursor = SITES.load(Acquire) as *const LockSite;
+    while !cursor.is_null() {
+        // Safety: published nodes are never freed.
+        let site = unsafe { &*cursor };
+        stats.push(site.stats());
+        cursor = site.next;
+    }
+    stats.sort_by(|a, b| {
+        (b.contended, b.spin_rounds, b.acquisitions).cmp(&(
+            a.contended,
+            a.spin_rounds,
+            a.acquisitions,
+        ))
+    });
+    stats
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_report_after_contended_run() {
+        use crate::{profiling, SpinLock};
+        use std::thread;
+        use std::time::Duration;
+
+        let hot = SpinLock::with_name("test.hot", 0u64);
+        let twin = SpinLock::with_name("test.hot", 0u64);
+        let cold = SpinLock::with_name("test.cold", 0u64);
+
+        thread::scope(|s| {
+            // Hold the lock so the waiter is guaranteed to contend at least once.
+            let held = hot.lock();
+            let waiter = s.spawn(|| *hot.lock() += 1);
+            thread::sleep(Duration::from_millis(20));
+            drop(held);
+            waiter.join().unwrap();
+
+            for _ in 0..4 {
+                s.spawn(|| {
+                    for _ in 0..1_000 {
+                        *hot.lock() += 1;
+                    }
+                });
+            }
+        });
+        *twin.lock() += 1;
+        *cold.lock() += 1;
+
+        let stats = profiling::report();
+        let site = |name| stats.iter().position(|s| s.name == name).unwrap();
+        let (hot, cold) = (&stats[site("test.hot")], &stats[site("test.cold")]);
+        assert_eq!(
+            hot.acquisitions,
+            1 + 1 + 4_000 + 1,
+            "both locks named test.hot aggregate"
+        );
+        assert!(hot.contended >= 1 && hot.spin_rounds >= hot.contended);
+        assert_eq!(
+            (cold.acquisitions, cold.contended, cold.spin_rounds),
+            (1, 0, 0)
+        );
+        assert!(site("test.hot") < site("test.cold"), "hottest first");
+        assert_eq!(stats.iter().filter(|s| s.name == "test.hot").count(), 1);
+    }
+}
@@ -0,0 +1,608 @@
+//! # RawSpinLock
+//!
+//! The bare locking mechanism behind [`SpinLock`](crate::SpinLock), decoupled from
+//! any protected data.
+//!
+//! [`RawSpinLock`] is just the atomic flag plus the acquire/release protocol: it
+//! owns no [`UnsafeCell`](core::cell::UnsafeCell) and hands out no guards. Use it
+//! when the data it protects lives elsewhere — several arrays guarded by one lock,
+//! state embedded in a foreign struct — or to build your own guard types. This is
+//! the same split as `lock_api::RawMutex` vs. `lock_api::Mutex`.
+//!
+//! `SpinLock<T>` is implemented on top of it, so both share the exact same
+//! contention behavior (exponential [`BackOff`]) and memory ordering
+//! (Acquire on acquisition, Release on release).
+//!
+//! ## `lock_api` integration
+//!
+//! With the **`lock_api`** feature, `RawSpinLock` implements
+//! [`lock_api::RawMutex`] (and, with `std`, [`lock_api::RawMutexTimed`] using
+//! `std::time::Instant` deadlines), and [`SpinMutex<T>`] is exported as
+//! `lock_api::Mutex<RawSpinLock, T>`. That gives you `MutexGuard::map`,
+//! `const_new`, and compatibility with crates generic over `lock_api` mutexes,
+//! while keeping this crate's spin implementation.
+//!
+//! The guard marker is [`lock_api::GuardSend`]: releasing a `RawSpinLock` is a
+//! plain atomic store with no notion of an owning thread, so a guard may be
+//! dropped on a different thread than the one that acquired it.
+//!
+//! ## Fenced acquisition
+//!
+//! With the **`fenced-acquire`** feature, [`RawSpinLock::lock_fenced`] (and
+//! `SpinLock::lock_fenced`) take the lock with `swap(true, Relaxed)` and issue
+//! `fence(Acquire)` only after the swap that wins. The observable semantics are
+//! identical to an `Acquire` swap: the winning swap reads the `false` written by
+//! the previous holder's `Release` unlock, and an acquire fence sequenced after
+//! a load that reads a release store synchronizes with that store, so all of
+//! the previous holder's writes are visible afterwards. Failed retries need no
+//! ordering at all, since they grant no access.
+//!
+//! This is a performance experiment for weakly ordered targets (e.g. aarch64),
+//! where one fence after a run of relaxed RMWs can be cheaper than an acquire
+//! RMW per attempt on some cores. On x86 every RMW is already a full barrier,
+//! so expect no difference; measure with the `fenced` group of
+//! `benches/contention.rs` before switching. ThreadSanitizer does not model
+//! standalone fences and may report false races on this path.
+//!
+//! ## Example
+//! ```rust
+//! use axiom_spinlock::RawSpinLock;
+//!
+//! static LOCK: RawSpinLock = RawSpinLock::new();
+//!
+//! LOCK.lock();
+//! // ... touch the data this lock protects ...
+//! unsafe { LOCK.unlock() };
+//! assert!(!LOCK.is_locked());
+//! ```
+
+use core::sync::atomic::{
+    AtomicBool, Ordering,
+    Ordering::{Acquire, Relaxed, Release},
+};
+
+use crate::BackOff;
+
+/// How long a waiting `lock()` may go without acquiring before it panics with
+/// "possible deadlock", in builds with `debug_assertions` and `std` (the check
+/// is absent otherwise).
+///
+/// A best-effort catch for self-deadlocks and locks that are never released,
+/// without the holder tracking of the `deadlock-detection` feature. It is
+/// measured on the wall clock, once per backoff round, so a preempted holder
+/// or a long critical section does not trip it; only a wait far beyond any
+/// legitimate spinlock hold does.
+#[cfg(feature = "std")]
+pub const DEBUG_DEADLOCK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Pause iterations after which a waiting `lock()` panics with "possible
+/// deadlock", in `no_std` builds with `debug_assertions`, which have no clock
+/// to measure `DEBUG_DEADLOCK_TIMEOUT`-style waits.
+///
+/// Counted over all backoff rounds of one wait, so the bound is the same
+/// whatever the lock's backoff curve: even a one-pause-per-attempt
+/// [`new_low_latency`](crate::SpinLock::new_low_latency) lock only trips it
+/// after tens of seconds of waiting on CPUs with fast pause instructions.
+#[cfg(not(feature = "std"))]
+pub const DEBUG_DEADLOCK_PAUSES: u64 = 1 << 32;
+
+#[cfg(all(test, debug_assertions, feature = "std"))]
+std::thread_local! {
+    /// The calling thread's override of [`DEBUG_DEADLOCK_TIMEOUT`], so tests need
+    /// not wait out the real timeout.
+    static DEBUG_DEADLOCK_TIMEOUT_OVERRIDE: core::cell::Cell<Option<std::time::Duration>> =
+        const { core::cell::Cell::new(None) };
+}
+
+/// How long `lock()` may wait before its debug deadlock check panics.
+#[cfg(all(debug_assertions, feature = "std"))]
+#[inline]
+fn debug_deadlock_timeout() -> std::time::Duration {
+    #[cfg(test)]
+    if let Some(timeout) = DEBUG_DEADLOCK_TIMEOUT_OVERRIDE.get() {
+        return timeout;
+    }
+    DEBUG_DEADLOCK_TIMEOUT
+}
+
+/// A spin-based lock that protects no data of its own.
+///
+/// Acquiring it is [`lock`](RawSpinLock::lock)/[`try_lock`](RawSpinLock::try_lock);
+/// releasing it is the `unsafe` [`unlock`](RawSpinLock::unlock), since the raw lock
+/// cannot know whether the caller actually holds it.
+#[repr(transparent)]
+pub struct RawSpinLock {
+    locked: AtomicBool,
+}
+
+impl RawSpinLock {
+    /// Creates a new, unlocked [`RawSpinLock`].
+    #[inline(always)]
+    pub const fn new() -> Self {
+        RawSpinLock {
+            locked: AtomicBool::new(false),
+        }
+    }
+
+    /// Creates a new [`RawSpinLock`] that is already held.
+    ///
+    /// Whoever is responsible for the initial hold must eventually release it
+    /// with [`unlock`](Self::unlock).
+    #[inline(always)]
+    pub const fn new_locked() -> Self {
+        RawSpinLock {
+            locked: AtomicBool::new(true),
+        }
+    }
+
+    /// Views an existing [`AtomicBool`] as a [`RawSpinLock`], without moving it.
+    ///
+    /// `RawSpinLock` is `#[repr(transparent)]` over its `AtomicBool`, so this is a
+    /// plain reference cast: `true` means held, `false` means free. It is the
+    /// zero-copy way to put a lock around preallocated memory — keep the flag
+    /// wherever is convenient and pair it with the region yourself (see
+    /// [`SpinLock`](crate::SpinLock#wrapping-preallocated-data) for why a
+    /// `SpinLock<T>` cannot be conjured out of a bare `&mut T`). Every access to
+    /// the flag must go through the lock protocol for the pairing to be sound.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::RawSpinLock;
+    /// use core::sync::atomic::AtomicBool;
+    ///
+    /// static FLAG: AtomicBool = AtomicBool::new(false);
+    ///
+    /// let lock = RawSpinLock::from_atomic(&FLAG);
+    /// lock.lock();
+    /// assert!(FLAG.load(core::sync::atomic::Ordering::Relaxed));
+    /// unsafe { lock.unlock() };
+    /// ```
+    #[inline(always)]
+    pub const fn from_atomic(flag: &AtomicBool) -> &RawSpinLock {
+        // Safety: `RawSpinLock` is `repr(transparent)` over `AtomicBool`, so the
+        // two have the same layout and validity, and the lifetime is preserved.
+        unsafe { &*(flag as *const AtomicBool as *const RawSpinLock) }
+    }
+
+    /// Acquires the lock, spinning until it becomes available.
+    ///
+    /// Uses an exponential [`BackOff`] to reduce contention.
+    ///
+    /// # Panics
+    /// In builds with `debug_assertions`, panics with "possible deadlock" after
+    /// waiting `DEBUG_DEADLOCK_TIMEOUT` (or `DEBUG_DEADLOCK_PAUSES` pause
+    /// iterations without `std`) without acquiring.
+    #[inline]
+    pub fn lock(&self) {
+        self.lock_counted();
+    }
+
+    /// Acquires the lock, returning how many backoff rounds it took.
+    ///
+    /// Only the first, uncontended `swap` is inlined into callers; everything
+    /// else (including the [`BackOff`] construction) lives in the out-of-line
+    /// [`lock_contended`](Self::lock_contended).
+    #[inline]
+    pub(crate) fn lock_counted(&self) -> u32 {
+        self.lock_counted_with(BackOff::new)
+    }
+
+    /// Like [`lock_counted`](Self::lock_counted), but waits with the [`BackOff`]
+    /// built by `backoff` (only called once the first `swap` fails).
+    #[inline]
+    pub(crate) fn lock_counted_with(&self, backoff: impl FnOnce() -> BackOff) -> u32 {
+        if !self.locked.swap(true, Acquire) {
+            return 0;
+        }
+        self.lock_contended(backoff, Acquire)
+    }
+
+    /// Acquires the lock like [`lock`](Self::lock), but with `Relaxed` swaps and
+    /// a single `Acquire` fence once the swap that takes the lock succeeds (only
+    /// available with the `fenced-acquire` feature).
+    ///
+    /// See [fenced acquisition](crate::raw#fenced-acquisition) for why the two
+    /// are equivalent.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::RawSpinLock;
+    ///
+    /// let lock = RawSpinLock::new();
+    /// lock.lock_fenced();
+    /// assert!(lock.is_locked());
+    /// unsafe { lock.unlock() };
+    /// ```
+    #[cfg(feature = "fenced-acquire")]
+    #[inline]
+    pub fn lock_fenced(&self) {
+        self.lock_fenced_counted();
+    }
+
+    /// Like [`lock_counted`](Self::lock_counted), acquiring through
+    /// [`lock_fenced`](Self::lock_fenced)'s `Relaxed` swaps plus a fence.
+    #[cfg(feature = "fenced-acquire")]
+    #[inline]
+    pub(crate) fn lock_fenced_counted(&self) -> u32 {
+        let rounds = if !self.locked.swap(true, Relaxed) {
+            0
+        } else {
+            self.lock_contended(BackOff::new, Relaxed)
+        };
+        core::sync::atomic::fence(Acquire);
+        rounds
+    }
+
+    /// Slow path of [`lock_counted`](Self::lock_counted), entered after the first
+    /// `swap` found the lock held; `order` is the ordering of the retried swaps.
+    #[cold]
+    #[inline(never)]
+    fn lock_contended(&self, backoff: impl FnOnce() -> BackOff, order: Ordering) -> u32 {
+        let backoff = backoff();
+        let mut spins: u32 = 0;
+        #[cfg(all(debug_assertions, feature = "std"))]
+        let waiting_since = std::time::Instant::now();
+        #[cfg(all(debug_assertions, not(feature = "std")))]
+        let mut paused: u64 = 0;
+        loop {
+            #[cfg(all(debug_assertions, feature = "std"))]
+            {
+                let waited = waiting_since.elapsed();
+                assert!(
+                    waited < debug_deadlock_timeout(),
+                    "possible deadlock: waited {waited:?} without acquiring the lock"
+                );
+            }
+            #[cfg(all(debug_assertions, not(feature = "std")))]
+            {
+                assert!(
+                    paused < DEBUG_DEADLOCK_PAUSES,
+                    "possible deadlock: {paused} pause iterations without acquiring the lock"
+                );
+                paused = paused.saturating_add(u64::from(backoff.current()));
+            }
+            backoff.wait();
+            spins = spins.saturating_add(1);
+            if !self.locked.swap(true, order) {
+                return spins;
+            }
+        }
+    }
+
+    /// Attempts to acquire the lock without blocking.
+    ///
+    /// Returns `true` if the lock was free and is now held by the caller.
+    ///
+    /// A failed attempt (`false`) establishes no happens-before relationship with
+    /// the current holder, so it grants no access to the protected data.
+    ///
+    /// It is also never spurious: `false` means the lock was held at the moment
+    /// of the `swap`, as documented in the "No spurious failures" section of
+    /// [`SpinLock::try_lock`](crate::SpinLock::try_lock).
+    #[inline(always)]
+    pub fn try_lock(&self) -> bool {
+        !self.locked.swap(true, Acquire)
+    }
+
+    /// Releases the lock.
+    ///
+    /// # Safety
+    /// - Only call this if you *own* the lock (i.e. you acquired it and have not
+    ///   released it since).
+    /// - Misuse can cause data races or UB in whatever the lock protects.
+    #[inline(always)]
+    pub unsafe fn unlock(&self) {
+        self.locked.store(false, Release);
+    }
+
+    /// Releases the lock with a `SeqCst` store, for `SpinLock::new_seqcst`.
+    ///
+    /// # Safety
+    /// Same as [`unlock`](Self::unlock).
+    #[inline(always)]
+    pub(crate) unsafe fn unlock_seqcst(&self) {
+        self.locked.store(false, Ordering::SeqCst);
+    }
+
+    /// Clears the flag with a swap in `order`, returning whether it was set, for
+    /// `SpinLock::force_unlock`.
+    #[cfg(feature = "recovery")]
+    #[inline(always)]
+    pub(crate) fn force_unlock(&self, order: Ordering) -> bool {
+        self.locked.swap(false, order)
+    }
+
+    /// Checks whether the lock is currently held.
+    #[inline(always)]
+    pub fn is_locked(&self) -> bool {
+        self.locked.load(Acquire)
+    }
+
+    /// Checks whether the lock is currently held, using a `Relaxed` load.
+    ///
+    /// See [`SpinLock::is_locked_relaxed`](crate::SpinLock::is_locked_relaxed).
+    #[inline(always)]
+    pub fn is_locked_relaxed(&self) -> bool {
+        self.locked.load(Relaxed)
+    }
+}
+
+/// A [`lock_api::Mutex`] backed by [`RawSpinLock`].
+///
+/// # Example
+/// ```
+/// use axiom_spinlock::raw::{SpinMutex, SpinMutexGuard};
+///
+/// static COUNTER: SpinMutex<u32> = SpinMutex::new(0);
+///
+/// *COUNTER.lock() += 1;
+/// let mapped = SpinMutexGuard::map(COUNTER.lock(), |v| v);
+/// assert_eq!(*mapped, 1);
+/// ```
+#[cfg(feature = "lock_api")]
+pub type SpinMutex<T> = lock_api::Mutex<RawSpinLock, T>;
+
+/// The guard type of [`SpinMutex`].
+#[cfg(feature = "lock_api")]
+pub type SpinMutexGuard<'a, T> = lock_api::MutexGuard<'a, RawSpinLock, T>;
+
+// Safety: `lock`/`try_lock` only succeed after an Acquire swap from `false` to
+// `true`, so at most one holder exists until `unlock` stores `false` with Release.
+#[cfg(feature = "lock_api")]
+unsafe impl lock_api::RawMutex for RawSpinLock {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = RawSpinLock::new();
+
+    type GuardMarker = lock_api::GuardSend;
+
+    #[inline]
+    fn lock(&self) {
+        RawSpinLock::lock(self)
+    }
+
+    #[inline]
+    fn try_lock(&self) -> bool {
+        RawSpinLock::try_lock(self)
+    }
+
+    #[inline]
+    unsafe fn unlock(&self) {
+        RawSpinLock::unlock(self)
+    }
+
+    #[inline]
+    fn is_locked(&self) -> bool {
+        RawSpinLock::is_locked(self)
+    }
+}
+
+// Safety: timed acquisition goes through the same `try_lock` as above.
+#[cfg(all(feature = "lock_api", feature = "std"))]
+unsafe impl lock_api::RawMutexTimed for RawSpinLock {
+    type Duration = core::time::Duration;
+    type Instant = std::time::Instant;
+
+    #[inline]
+    fn try_lock_for(&self, timeout: Self::Duration) -> bool {
+        match std::time::Instant::now().checked_add(timeout) {
+            Some(deadline) => self.try_lock_until(deadline),
+            // Unrepresentable deadline: treat it as "wait forever".
+            None => {
+                RawSpinLock::lock(self);
+                true
+            }
+        }
+    }
+
+    #[inline]
+    fn try_lock_until(&self, timeout: Self::Instant) -> bool {
+        let backoff = BackOff::new();
+        loop {
+            if RawSpinLock::try_lock(self) {
+                return true;
+            }
+            if std::time::Instant::now() >= timeout {
+                return false;
+            }
+            backoff.wait();
+        }
+    }
+}
+
+impl Default for RawSpinLock {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_raw_lock_unlock() {
+        use crate::RawSpinLock;
+
+        let lock = RawSpinLock::new();
+        assert!(lock.try_lock());
+        assert!(lock.is_locked());
+        assert!(!lock.try_lock(), "Lock should not be acquirable while held");
+
+        unsafe { lock.unlock() };
+        assert!(!lock.is_locked());
+
+        lock.lock();
+        assert!(lock.is_locked());
+        unsafe { lock.unlock() };
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_raw_lock_guards_separate_data() {
+        use crate::RawSpinLock;
+        use core::cell::UnsafeCell;
+        use std::sync::Arc;
+        use std::thread;
+
+        // Two independent arrays kept consistent by a single raw lock.
+        struct Shared {
+            lock: RawSpinLock,
+            left: UnsafeCell<[u64; 4]>,
+            right: UnsafeCell<[u64; 4]>,
+        }
+        unsafe impl Sync for Shared {}
+
+        let shared = Arc::new(Shared {
+            lock: RawSpinLock::new(),
+            left: UnsafeCell::new([0; 4]),
+            right: UnsafeCell::new([0; 4]),
+        });
+
+        let mut handles = vec![];
+        for i in 0..4 {
+            let shared = shared.clone();
+            handles.push(thread::spawn(move || {
+                for _ in 0..5_000 {
+                    shared.lock.lock();
+                    unsafe {
+                        (*shared.left.get())[i] += 1;
+                        (*shared.right.get())[3 - i] += 1;
+                        shared.lock.unlock();
+                    }
+                }
+            }));
+        }
+        for h in handles {
+            h.join().unwrap();
+        }
+
+        unsafe {
+            assert_eq!(*shared.left.get(), [5_000; 4]);
+            assert_eq!(*shared.right.get(), [5_000; 4]);
+        }
+    }
+
+    #[cfg(all(feature = "lock_api", feature = "std"))]
+    #[test]
+    fn test_lock_api_mutex() {
+        use crate::raw::{SpinMutex, SpinMutexGuard};
+        use crate::RawSpinLock;
+        use std::time::Duration;
+
+        static CONST_MUTEX: SpinMutex<u8> = SpinMutex::const_new(RawSpinLock::new(), 1);
+        assert_eq!(*CONST_MUTEX.lock(), 1);
+
+        let mutex = SpinMutex::new((1u32, 2u32));
+        {
+            let mut second = SpinMutexGuard::map(mutex.lock(), |pair| &mut pair.1);
+            *second += 40;
+        }
+        assert_eq!(*mutex.lock(), (1, 42));
+
+        let held = mutex.lock();
+        assert!(mutex.is_locked());
+        assert!(mutex.try_lock_for(Duration::from_millis(5)).is_none());
+        drop(held);
+        assert!(mutex.try_lock_for(Duration::from_millis(5)).is_some());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_from_atomic_wraps_stack_region() {
+        use crate::RawSpinLock;
+        use core::sync::atomic::AtomicBool;
+        use std::thread;
+
+        // A preallocated region and a separate flag, neither of which moves.
+        let mut region = [0u64; 4];
+        let flag = AtomicBool::new(false);
+        let lock = RawSpinLock::from_atomic(&flag);
+        let base = region.as_mut_ptr() as usize;
+
+        thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(move || {
+                    for _ in 0..1_000 {
+                        lock.lock();
+                        // Safety: the region outlives the scope and every access holds `lock`.
+                        unsafe {
+                            for i in 0..4 {
+                                *(base as *mut u64).add(i) += 1;
+                            }
+                            lock.unlock();
+                        }
+                    }
+                });
+            }
+        });
+
+        assert!(!flag.into_inner());
+        assert_eq!(region, [4_000; 4]);
+    }
+
+    #[cfg(all(feature = "std", debug_assertions, not(feature = "deadlock-detection")))]
+    #[test]
+    fn test_debug_self_deadlock_panics() {
+        use crate::SpinLock;
+        use std::thread;
+        use std::time::Duration;
+
+        let lock = SpinLock::new(0u8);
+        let result = thread::scope(|s| {
+            s.spawn(|| {
+                super::DEBUG_DEADLOCK_TIMEOUT_OVERRIDE.set(Some(Duration::from_millis(200)));
+                let _first = lock.lock();
+                let _second = lock.lock();
+            })
+            .join()
+        });
+
+        let message = *result.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.contains("possible deadlock"), "{message}");
+        assert!(!lock.is_locked(), "the unwinding thread releases its guard");
+    }
+
+    #[cfg(all(feature = "std", debug_assertions))]
+    #[test]
+    fn test_debug_long_hold_is_not_a_deadlock() {
+        use crate::SpinLock;
+        use std::sync::Barrier;
+        use std::thread;
+        use std::time::Duration;
+
+        let lock = SpinLock::new(0u8);
+        let held = Barrier::new(2);
+        thread::scope(|s| {
+            s.spawn(|| {
+                let _guard = lock.lock();
+                held.wait();
+                thread::sleep(Duration::from_millis(1_500));
+            });
+            held.wait();
+            // Waits out the whole hold, escalating to the longest rounds.
+            *lock.lock() += 1;
+        });
+        assert_eq!(*lock.lock(), 1);
+    }
+
+    #[cfg(all(feature = "std", feature = "fenced-acquire"))]
+    #[test]
+    fn test_lock_fenced_excludes() {
+        use crate::SpinLock;
+        use std::thread;
+
+        let lock = SpinLock::new(0u64);
+        thread::scope(|s| {
+            for t in 0..4 {
+                let lock = &lock;
+                s.spawn(move || {
+                    for _ in 0..5_000 {
+                        // Mix both acquire paths on the same lock.
+                        let mut guard = if t % 2 == 0 { lock.lock_fenced() } else { lock.lock() };
+                        *guard += 1;
+                    }
+                });
+            }
+        });
+        assert_eq!(lock.into_inner(), 20_000);
+    }
+}
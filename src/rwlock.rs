@@ -0,0 +1,442 @@
+//! # RwSpinLock
+//!
+//! A spin-based reader-writer lock: any number of readers **or** one writer,
+//! plus an *upgradable* reader for read-then-maybe-write sections.
+//!
+//! - 📖 [`RwSpinLock::read`] — shared access, concurrent with other readers and
+//!   with an upgradable reader.
+//! - ✍️ [`RwSpinLock::write`] — exclusive access.
+//! - 🔼 [`RwSpinLock::upgradable_read`] — shared access that excludes writers
+//!   and other upgradable readers, and can turn into a write guard with
+//!   [`RwSpinUpgradableGuard::upgrade`] *without* releasing in between, so the
+//!   value it inspected cannot change before the write.
+//!
+//! The whole state is one [`AtomicUsize`]: a writer bit, an upgradable bit, and
+//! the reader count above them. Waiting uses the same exponential [`BackOff`] as
+//! [`SpinLock`](crate::SpinLock), with Acquire on acquisition and Release on
+//! release.
+//!
+//! ## Caveats
+//! - Not fair: a steady stream of readers can starve writers and a pending
+//!   upgrade alike.
+//! - Not reentrant: an upgradable guard is not counted as a reader, so
+//!   [`upgrade`](RwSpinUpgradableGuard::upgrade) only waits for *other* readers
+//!   — but if the upgrading thread also holds a separate read guard on the same
+//!   lock, the upgrade waits for itself forever.
+//!
+//! ## Example
+//! ```rust
+//! use axiom_spinlock::RwSpinLock;
+//!
+//! static CONFIG: RwSpinLock<u32> = RwSpinLock::new(1);
+//!
+//! assert_eq!(*CONFIG.read(), 1);
+//!
+//! let candidate = CONFIG.upgradable_read();
+//! if *candidate < 5 {
+//!     let mut writer = candidate.upgrade();
+//!     *writer = 5;
+//! }
+//! assert_eq!(*CONFIG.read(), 5);
+//! ```
+
+use core::cell::UnsafeCell;
+use core::ops::{Deref, DerefMut};
+use core::sync::atomic::{
+    AtomicUsize,
+    Ordering::{Acquire, Relaxed, Release},
+};
+
+use crate::BackOff;
+
+/// Set while a writer holds the lock.
+const WRITER: usize = 1;
+
+/// Set while an upgradable reader holds the lock.
+const UPGRADABLE: usize = 1 << 1;
+
+/// One reader; the reader count lives in the bits above the two flags.
+const READER: usize = 1 << 2;
+
+/// A spin-based reader-writer lock with upgradable reads.
+///
+/// See the [module-level documentation](crate::rwlock) for the protocol and caveats.
+pub struct RwSpinLock<T> {
+    state: AtomicUsize,
+    data: UnsafeCell<T>,
+}
+
+/// Shared access to the data of a [`RwSpinLock`], released on drop.
+pub struct RwSpinReadGuard<'a, T> {
+    lock: &'a RwSpinLock<T>,
+}
+
+/// Exclusive access to the data of a [`RwSpinLock`], released on drop.
+pub struct RwSpinWriteGuard<'a, T> {
+    lock: &'a RwSpinLock<T>,
+}
+
+/// Shared access that excludes writers and other upgradable readers, and can be
+/// [upgraded](Self::upgrade) to a [`RwSpinWriteGuard`] atomically.
+pub struct RwSpinUpgradableGuard<'a, T> {
+    lock: &'a RwSpinLock<T>,
+}
+
+impl<T> RwSpinLock<T> {
+    /// Creates a new, unlocked [`RwSpinLock`] wrapping the given data.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::RwSpinLock;
+    ///
+    /// let lock = RwSpinLock::new(3);
+    /// assert_eq!(*lock.read(), 3);
+    /// ```
+    #[inline(always)]
+    pub const fn new(data: T) -> Self {
+        RwSpinLock {
+            state: AtomicUsize::new(0),
+            data: UnsafeCell::new(data),
+        }
+    }
+
+    /// Consumes the lock and returns the protected data.
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+
+    /// Acquires shared access, spinning while a writer holds the lock.
+    ///
+    /// Concurrent with other readers and with an upgradable reader.
+    #[inline]
+    pub fn read(&self) -> RwSpinReadGuard<'_, T> {
+        let backoff = BackOff::new();
+        loop {
+            if let Some(guard) = self.try_read() {
+                return guard;
+            }
+            // Wait for the writer to leave before touching the counter again.
+            while self.state.load(Relaxed) & WRITER != 0 {
+                backoff.wait();
+            }
+        }
+    }
+
+    /// Attempts to acquire shared access without blocking.
+    #[inline]
+    pub fn try_read(&self) -> Option<RwSpinReadGuard<'_, T>> {
+        let state = self.state.fetch_add(READER, Acquire);
+        assert!(state < usize::MAX / 2, "RwSpinLock reader count overflow");
+        if state & WRITER != 0 {
+            self.state.fetch_sub(READER, Relaxed);
+            None
+        } else {
+            Some(RwSpinReadGuard { lock: self })
+        }
+    }
+
+    /// Acquires exclusive access, spinning until there are no readers, no
+    /// upgradable reader and no writer.
+    #[inline]
+    pub fn write(&self) -> RwSpinWriteGuard<'_, T> {
+        let backoff = BackOff::new();
+        loop {
+            if let Some(guard) = self.try_write() {
+                return guard;
+            }
+            backoff.wait();
+        }
+    }
+
+    /// Attempts to acquire exclusive access without blocking.
+    #[inline]
+    pub fn try_write(&self) -> Option<RwSpinWriteGuard<'_, T>> {
+        self.state
+            .compare_exchange(0, WRITER, Acquire, Relaxed)
+            .ok()
+            .map(|_| RwSpinWriteGuard { lock: self })
+    }
+
+    /// Acquires upgradable shared access, spinning while a writer or another
+    /// upgradable reader holds the lock.
+    ///
+    /// Plain readers may still come and go; writers wait until the guard is
+    /// dropped or [upgraded](RwSpinUpgradableGuard::upgrade) and released.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::RwSpinLock;
+    ///
+    /// let lock = RwSpinLock::new(vec![1, 2]);
+    /// let view = lock.upgradable_read();
+    /// assert_eq!(lock.read().len(), 2); // readers are still admitted
+    /// assert!(lock.try_write().is_none());
+    /// view.upgrade().push(3);
+    /// assert_eq!(*lock.read(), [1, 2, 3]);
+    /// ```
+    #[inline]
+    pub fn upgradable_read(&self) -> RwSpinUpgradableGuard<'_, T> {
+        let backoff = BackOff::new();
+        loop {
+            if let Some(guard) = self.try_upgradable_read() {
+                return guard;
+            }
+            backoff.wait();
+        }
+    }
+
+    /// Attempts to acquire upgradable shared access without blocking.
+    #[inline]
+    pub fn try_upgradable_read(&self) -> Option<RwSpinUpgradableGuard<'_, T>> {
+        let mut state = self.state.load(Relaxed);
+        loop {
+            if state & (WRITER | UPGRADABLE) != 0 {
+                return None;
+            }
+            // Retry only when the reader count moved underneath us.
+            match self
+                .state
+                .compare_exchange_weak(state, state | UPGRADABLE, Acquire, Relaxed)
+            {
+                Ok(_) => return Some(RwSpinUpgradableGuard { lock: self }),
+                Err(current) => state = current,
+            }
+        }
+    }
+
+    /// Returns a mutable reference to the data; no locking is needed since the
+    /// borrow is exclusive.
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> &mut T {
+        self.data.get_mut()
+    }
+
+    /// Returns the number of readers currently holding the lock (racy; for
+    /// diagnostics only). An upgradable reader is not included.
+    #[inline]
+    pub fn reader_count(&self) -> usize {
+        self.state.load(Relaxed) / READER
+    }
+
+    /// Checks whether a writer currently holds the lock (racy; for diagnostics only).
+    #[inline]
+    pub fn is_locked_exclusive(&self) -> bool {
+        self.state.load(Relaxed) & WRITER != 0
+    }
+}
+
+impl<'a, T> RwSpinUpgradableGuard<'a, T> {
+    /// Turns this guard into exclusive access, waiting for the current readers
+    /// to drain.
+    ///
+    /// No writer or other upgradable reader can get in between: the lock is
+    /// never released during the upgrade, so whatever was read through this
+    /// guard is still current when the write guard is returned.
+    #[inline]
+    pub fn upgrade(self) -> RwSpinWriteGuard<'a, T> {
+        let backoff = BackOff::new();
+        let mut this = self;
+        loop {
+            match Self::try_upgrade(this) {
+                Ok(guard) => return guard,
+                Err(guard) => this = guard,
+            }
+            backoff.wait();
+        }
+    }
+
+    /// Attempts to upgrade without waiting; fails (returning the guard) while
+    /// any reader holds the lock.
+    #[inline]
+    pub fn try_upgrade(this: Self) -> Result<RwSpinWriteGuard<'a, T>, Self> {
+        match this
+            .lock
+            .state
+            .compare_exchange(UPGRADABLE, WRITER, Acquire, Relaxed)
+        {
+            Ok(_) => {
+                let lock = this.lock;
+                core::mem::forget(this);
+                Ok(RwSpinWriteGuard { lock })
+            }
+            Err(_) => Err(this),
+        }
+    }
+}
+
+impl<T> Deref for RwSpinReadGuard<'_, T> {
+    type Target = T;
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        // Safety: readers exclude writers, so only shared access exists.
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+impl<T> Deref for RwSpinUpgradableGuard<'_, T> {
+    type Target = T;
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        // Safety: the upgradable bit excludes writers, so only shared access exists.
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+impl<T> Deref for RwSpinWriteGuard<'_, T> {
+    type Target = T;
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        // Safety: the writer bit grants exclusive access.
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+impl<T> DerefMut for RwSpinWriteGuard<'_, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        // Safety: the writer bit grants exclusive access.
+        unsafe { &mut *self.lock.data.get() }
+    }
+}
+
+impl<T> Drop for RwSpinReadGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.lock.state.fetch_sub(READER, Release);
+    }
+}
+
+impl<T> Drop for RwSpinUpgradableGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.lock.state.fetch_sub(UPGRADABLE, Release);
+    }
+}
+
+impl<T> Drop for RwSpinWriteGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        // Readers that bounced off the writer bit may have briefly bumped the
+        // count, so clear only our bit instead of storing zero.
+        self.lock.state.fetch_sub(WRITER, Release);
+    }
+}
+
+// Safety: the lock hands out `&T` to several threads at once (readers) and `&mut T`
+// to one thread at a time (writer), exactly like `std::sync::RwLock`.
+unsafe impl<T: Send> Send for RwSpinLock<T> {}
+unsafe impl<T: Send + Sync> Sync for RwSpinLock<T> {}
+
+impl<T: Default> Default for RwSpinLock<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_read_write_exclusion() {
+        use crate::RwSpinLock;
+
+        let lock = RwSpinLock::new(0u32);
+        let r1 = lock.read();
+        let r2 = lock.read();
+        assert_eq!(lock.reader_count(), 2);
+        assert!(lock.try_write().is_none());
+        drop((r1, r2));
+
+        let mut w = lock.write();
+        *w += 1;
+        assert!(lock.try_read().is_none());
+        assert!(lock.try_upgradable_read().is_none());
+        drop(w);
+        assert_eq!(*lock.read(), 1);
+        assert_eq!(lock.reader_count(), 0);
+    }
+
+    #[test]
+    fn test_upgradable_excludes_writers_and_upgradables() {
+        use crate::rwlock::RwSpinUpgradableGuard;
+        use crate::RwSpinLock;
+
+        let lock = RwSpinLock::new(1u32);
+        let up = lock.upgradable_read();
+        assert!(lock.try_upgradable_read().is_none());
+        assert!(lock.try_write().is_none());
+
+        let reader = lock
+            .try_read()
+            .expect("readers coexist with an upgradable reader");
+        let up = match RwSpinUpgradableGuard::try_upgrade(up) {
+            Ok(_) => panic!("upgraded while a reader is still active"),
+            Err(up) => up,
+        };
+        drop(reader);
+
+        let mut w = RwSpinUpgradableGuard::try_upgrade(up).ok().unwrap();
+        *w += 1;
+        drop(w);
+        assert_eq!(lock.into_inner(), 2);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_upgrade_races_writer() {
+        use crate::RwSpinLock;
+        use std::sync::atomic::{AtomicBool, Ordering::SeqCst};
+        use std::thread;
+        use std::time::Duration;
+
+        let lock = RwSpinLock::new(1u32);
+        let writer_done = AtomicBool::new(false);
+
+        thread::scope(|s| {
+            let up = lock.upgradable_read();
+            let seen = *up;
+
+            s.spawn(|| {
+                *lock.write() *= 10;
+                writer_done.store(true, SeqCst);
+            });
+            thread::sleep(Duration::from_millis(20));
+            assert!(
+                !writer_done.load(SeqCst),
+                "writer got in while upgradable was held"
+            );
+
+            // The writer must not slip in between the read and the write.
+            let mut w = up.upgrade();
+            assert_eq!(*w, seen);
+            *w += 1;
+        });
+
+        assert_eq!(lock.into_inner(), 20);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_concurrent_read_modify_write() {
+        use crate::RwSpinLock;
+        use std::thread;
+
+        let lock = RwSpinLock::new(0u64);
+        thread::scope(|s| {
+            for i in 0..6 {
+                let lock = &lock;
+                s.spawn(move || {
+                    for _ in 0..2_000 {
+                        match i % 3 {
+                            0 => *lock.upgradable_read().upgrade() += 1,
+                            1 => *lock.write() += 1,
+                            _ => assert!(*lock.read() <= 8_000),
+                        }
+                    }
+                });
+            }
+        });
+        assert_eq!(lock.into_inner(), 8_000);
+    }
+}
@@ -42,6 +42,73 @@
 //! }
 //! ```
 //!
+//! ## Wrapping preallocated data
+//! A `SpinLock<T>` stores its flag (and any feature-gated bookkeeping) *next to*
+//! the `T`, so it is strictly larger than `T` and a `&'static mut T` can never be
+//! reinterpreted as a `&'static SpinLock<T>`: there is no room for the flag, and
+//! writing one would clobber whatever follows the region. For zero-copy locking:
+//! - declare the storage as the lock from the start — [`SpinLock::new`] is
+//!   `const`, so `static BUF: SpinLock<[u8; N]>` costs no copy; or
+//! - keep the region where it is and guard it with a separate flag through
+//!   [`RawSpinLock::from_atomic`], pairing flag and data yourself.
+//!
+//! ## Guard tokens
+//! With the **`debug-guard-token`** feature, every release bumps a per-lock
+//! generation counter and each [`SpinGuard`] remembers the generation it was
+//! created in. Dropping a guard whose hold was already released — typically a
+//! guard kept in `ManuallyDrop` or `forget`-and-resurrected after a manual
+//! [`SpinLock::unlock`] — is detected: it does **not** release the lock again
+//! (which would free the *current* holder's hold), and trips a `debug_assert!`.
+//! It costs one `AtomicU32` per lock, a `u32` per guard and a counter update per
+//! release, so it is off by default.
+//!
+//! ## Deadlock detection
+//! `SpinLock` is not reentrant: locking it again from the thread that holds it
+//! spins forever. With the **`deadlock-detection`** feature (implies `std`), each
+//! lock records the [`ThreadId`](std::thread::ThreadId) of its holder and
+//! [`SpinLock::lock`] panics with a diagnostic instead of hanging. Contention
+//! between *different* threads is unaffected. The bookkeeping adds a small mutex
+//! to every lock and work to every acquire/release, so enable it in debug or test
+//! builds only and keep it off in release.
+//!
+//! Without the feature, builds with `debug_assertions` still catch the simplest
+//! cases: a contended acquisition that waits longer than
+//! [`DEBUG_DEADLOCK_TIMEOUT`](crate::raw::DEBUG_DEADLOCK_TIMEOUT) (10 s; a bound on
+//! pause iterations in `no_std` builds) without getting the lock panics with
+//! "possible deadlock". Release builds carry no such check.
+//!
+//! ## Adaptive spinning
+//! With the **`adaptive`** feature (implies `std`), each lock learns how long
+//! [`SpinLock::lock`] usually waits and spins accordingly before yielding, like
+//! the adaptive mutexes of some C libraries. The lock keeps an exponentially
+//! weighted moving average (EWMA) of the backoff rounds its acquisitions took,
+//! updated after every `lock()` as
+//! `avg ← avg + (rounds − avg) / 8` (stored scaled by 8 in an `AtomicU32`).
+//! Each `lock()` then spins for a budget of `2 · avg + 1` rounds and yields the
+//! thread on every round beyond it, but never starts yielding later than a
+//! default [`BackOff`] would (past [`BackOff::YIELD_THRESHOLD`]). Locks that are
+//! usually acquired quickly stop spinning far past their typical wait; locks
+//! with long waits keep the default behavior. Updates are plain relaxed loads and stores
+//! — a statistic, where a lost update is harmless — on the lock's own cache
+//! line. Low-latency locks ([`SpinLock::new_low_latency`]) are unaffected.
+//!
+//! ## Contention hint
+//! A lighter alternative to adaptive spinning: with the **`contention-hint`**
+//! feature, each lock keeps a tiny saturating counter (an `AtomicU8`, `0..=3`)
+//! raised by every contended [`SpinLock::lock`] and lowered by every
+//! uncontended one. A contended `lock` seeds its [`BackOff`] with
+//! `START_VALUE << hint`, so historically hot locks back off harder right away
+//! and cold locks start gently. It works in `no_std` and combines with
+//! `adaptive` (which then only decides when to yield).
+//!
+//! ## Secret data
+//! With the **`zeroize`** feature, [`SpinLock::zeroize`] wipes the data in place
+//! under the lock, and [`SpinLock::new_zeroizing`] wraps it in
+//! [`zeroize::Zeroizing`] so key material is wiped when the lock is dropped
+//! instead of lingering in freed memory. Both use the `zeroize` crate's volatile
+//! writes. Copies made while the lock was in use (moves out of a guard,
+//! `clone_data` snapshots, ...) are the caller's to wipe.
+//!
 //! ## When to Use
 //! - Embedded systems
 //! - Custom runtimes
@@ -54,218 +121,4710 @@
 //! - High-contention multi-core workloads (use a fair mutex instead)
 
 use core::cell::UnsafeCell;
-use core::ops::{Deref, DerefMut};
-use core::sync::atomic::{
-    AtomicBool,
-    Ordering::{Acquire, Release},
-};
+use core::fmt;
+use core::ops::{AddAssign, Deref, DerefMut, SubAssign};
+use core::pin::Pin;
+
+use crate::backoff::{BackOffConfig, Clock, GrowthKind};
+use crate::{BackOff, RawSpinLock};
+
+/// Bit of [`SpinLock`]'s `config` byte set by [`SpinLock::new_low_latency`].
+const CONFIG_LOW_LATENCY: u8 = 1 << 0;
+
+/// Bit of [`SpinLock`]'s `config` byte set by [`SpinLock::new_seqcst`].
+const CONFIG_SEQCST_RELEASE: u8 = 1 << 1;
+
+/// Bit of [`SpinLock`]'s `config` byte set by [`SpinLock::new_adaptive`].
+#[cfg(target_has_atomic = "32")]
+const CONFIG_ADAPT_BACKOFF: u8 = 1 << 2;
+
+/// Number of backoff rounds between two reads of the caller's clock in
+/// [`SpinLock::try_lock_for_ticks`].
+const TICK_SAMPLE_INTERVAL: u32 = 4;
+
+/// Fixed-point scale (and inverse weight) of the adaptive spin-round EWMA.
+#[cfg(feature = "adaptive")]
+const EWMA_SCALE: u32 = 8;
+
+/// Highest value of [`SpinLock::contention_hint`]: a hot lock starts its
+/// backoff at `BackOff::START_VALUE << 3`.
+#[cfg(feature = "contention-hint")]
+pub const MAX_CONTENTION_HINT: u8 = 3;
+
+/// Longest backoff round of [`SpinLock::try_lock_for`] and
+/// [`SpinLock::try_lock_for_counted`], in spin-loop iterations.
+///
+/// Caps the total busy-wait of `try_lock_for(spins)` at `spins` times this
+/// value, instead of letting late rounds escalate exponentially. It is below
+/// [`BackOff::YIELD_THRESHOLD`], so these calls never yield the thread either.
+pub const TRY_LOCK_FOR_MAX_ROUND_SPINS: u32 = BackOff::START_VALUE << 3;
+
+/// Highest starting backoff a [`SpinLock::new_adaptive`] lock learns, in
+/// spin-loop iterations.
+///
+/// Equal to the spin count above which a default [`BackOff`] starts yielding
+/// under `std`, so a learned first round never spins longer than the rounds
+/// a default backoff is willing to spin.
+pub const MAX_ADAPTIVE_BACKOFF_START: u32 = BackOff::START_VALUE << 5;
+
+/// Spin attempts made by [`SpinLock::lock_bounded`] before it parks the thread.
+#[cfg(feature = "parking")]
+const BOUNDED_SPIN_ATTEMPTS: usize = 16;
+
+/// Per-thread backoff salt of [`SpinLock::lock_desync`]: a hash of the thread id.
+#[cfg(feature = "std")]
+fn thread_salt() -> u32 {
+    use std::hash::{Hash, Hasher};
+
+    std::thread_local! {
+        static SALT: u32 = {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            std::thread::current().id().hash(&mut hasher);
+            hasher.finish() as u32
+        };
+    }
+    SALT.with(|salt| *salt)
+}
+
+/// OS-level parking state used as a starvation backstop by [`SpinLock::lock_bounded`].
+#[cfg(feature = "parking")]
+struct Parking {
+    mutex: std::sync::Mutex<()>,
+    condvar: std::sync::Condvar,
+    /// Threads currently inside [`SpinLock::lock_parked`]; releases only wake when nonzero.
+    waiters: core::sync::atomic::AtomicU32,
+}
+
+#[cfg(feature = "parking")]
+impl Parking {
+    const fn new() -> Self {
+        Parking {
+            mutex: std::sync::Mutex::new(()),
+            condvar: std::sync::Condvar::new(),
+            waiters: core::sync::atomic::AtomicU32::new(0),
+        }
+    }
+
+    /// Wakes one parked thread after the lock flag has been cleared, if any is parked.
+    ///
+    /// The common case (nobody parked) costs one fence and one load. Otherwise,
+    /// taking the mutex orders this wakeup after any parked thread's last check
+    /// of the flag, so a release can never slip between that check and its wait.
+    ///
+    /// Skipping the wakeup is safe by a store-buffering argument: a parker
+    /// increments `waiters`, fences, then checks the flag; the releaser clears
+    /// the flag, fences, then loads `waiters`. With both `SeqCst` fences, either
+    /// the parker sees the cleared flag (and takes the lock) or the releaser sees
+    /// the parker (and wakes it).
+    #[inline]
+    fn notify(&self) {
+        use core::sync::atomic::{fence, Ordering::{Relaxed, SeqCst}};
 
-use crate::BackOff;
+        fence(SeqCst);
+        if self.waiters.load(Relaxed) == 0 {
+            return;
+        }
+        let _parked = self.mutex.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        self.condvar.notify_one();
+    }
+}
 
 /// A simple spin-based mutual exclusion primitive.
 ///
 /// This lock uses atomic spinning with an exponential [`BackOff`] to minimize
 /// CPU usage under contention. It does not perform OS-level thread blocking.
 ///
+/// It is a [`RawSpinLock`] paired with the data it protects.
+///
 /// See the [module-level documentation](#) for examples and caveats.
+///
+/// # Layout
+/// `SpinLock<T>` is `#[repr(C)]` and starts with the same two fields in every
+/// configuration, so it can be mirrored by a C struct sharing the same memory:
+///
+/// | offset                                  | field    | type                                       |
+/// |-----------------------------------------|----------|--------------------------------------------|
+/// | `0`                                     | `data`   | `T`                                        |
+/// | [`LOCKED_OFFSET`](Self::LOCKED_OFFSET)  | `locked` | `AtomicBool` (one byte, `0` free, `1` held) |
+///
+/// `LOCKED_OFFSET` equals `size_of::<T>()`, since the flag has alignment 1.
+///
+/// For `SpinLock<u32>` that is `struct { uint32_t data; _Atomic _Bool locked; }`.
+/// The C side must follow the same protocol: acquire by atomically exchanging
+/// `locked` to `1` with acquire ordering until the old value was `0`, release by
+/// storing `0` with release ordering. Every other field comes after `locked`
+/// and is opaque to C: per-lock settings (such as the
+/// [low-latency](Self::new_low_latency) and [`SeqCst`](Self::new_seqcst)
+/// release modes and the [backoff start](Self::new_with_backoff)) and the state enabled by
+/// `adaptive`, `contention-hint`, `parking`, `deadlock-detection`,
+/// `profiling` or `debug-guard-token`. Let Rust initialize
+/// the lock (e.g. with [`init_at`](Self::init_at)) instead of setting it up from
+/// C, and never share a lock whose features rely on that extra state; those
+/// features also change the total size. Even without them the settings take a
+/// config byte and a 4-byte-aligned backoff start, so `SpinLock<u8>` is 8 bytes
+/// and `SpinLock<u32>` 12.
+#[repr(C)]
 pub struct SpinLock<T> {
     data: UnsafeCell<T>,
-    locked: AtomicBool,
+    raw: RawSpinLock,
+    /// Per-lock settings chosen by the constructors, one bit each (`CONFIG_*`),
+    /// so they cost one byte in total: [`SpinLock::new_low_latency`] makes
+    /// `lock` retry with a constant one-pause backoff, [`SpinLock::new_seqcst`]
+    /// makes releases store the flag with `SeqCst` and [`SpinLock::new_adaptive`]
+    /// makes `lock` learn `backoff_start`.
+    config: u8,
+    /// Spin count `lock`'s backoff starts at, set by [`SpinLock::new_with_backoff`]
+    /// and retuned by `lock` on [`SpinLock::new_adaptive`] locks. Retuning
+    /// needs 32-bit atomic loads and stores, so targets without 32-bit atomics
+    /// keep a fixed start and have no `new_adaptive`.
+    #[cfg(target_has_atomic = "32")]
+    backoff_start: core::sync::atomic::AtomicU32,
+    #[cfg(not(target_has_atomic = "32"))]
+    backoff_start: u32,
+    #[cfg(feature = "parking")]
+    parking: Parking,
+    /// Recent-contention counter seeding `lock`'s backoff, see [`SpinLock::contention_hint`].
+    #[cfg(feature = "contention-hint")]
+    contention: core::sync::atomic::AtomicU8,
+    /// EWMA of the backoff rounds `lock` took, scaled by [`EWMA_SCALE`].
+    #[cfg(feature = "adaptive")]
+    spin_ewma: core::sync::atomic::AtomicU32,
+    /// Thread currently holding the lock, for self-deadlock detection.
+    #[cfg(feature = "deadlock-detection")]
+    holder: std::sync::Mutex<Option<std::thread::ThreadId>>,
+    /// Registry site fed by `lock`, set by [`SpinLock::with_name`].
+    #[cfg(feature = "profiling")]
+    site: Option<&'static crate::profiling::LockSite>,
+    /// Bumped on every release; guards remember the value they were created with.
+    #[cfg(feature = "debug-guard-token")]
+    generation: core::sync::atomic::AtomicU32,
 }
 
-/// A guard that releases the [`SpinLock`] when dropped.
+/// A read-only guard returned by [`SpinLock::lock_shared_ref`].
 ///
-/// This is returned from [`SpinLock::lock`] and implements [`Deref`] and [`DerefMut`]
-/// to access the underlying data.
-pub struct SpinGuard<'a, T> {
-    guard: &'a SpinLock<T>,
+/// It holds the lock exclusively, exactly like a [`SpinGuard`], but only
+/// implements [`Deref`]: the type itself documents (and enforces) that the
+/// critical section does not mutate the data. It is *not* a shared read lock;
+/// other readers still wait.
+///
+/// ```compile_fail
+/// use axiom_spinlock::SpinLock;
+///
+/// let lock = SpinLock::new(0);
+/// let mut view = lock.lock_shared_ref();
+/// *view = 1; // no `DerefMut`
+/// ```
+pub struct SpinReadGuard<'a, T> {
+    inner: SpinGuard<'a, T>,
 }
 
-impl<'a, T> Drop for SpinGuard<'a, T> {
-    #[inline]
-    fn drop(&mut self) {
-        self.guard.locked.store(false, Release)
+impl<T> Deref for SpinReadGuard<'_, T> {
+    type Target = T;
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.inner
     }
 }
 
-impl<T> SpinLock<T> {
-    /// Creates a new [`SpinLock`] wrapping the given data.
-    ///
-    /// # Example
-    /// ```
-    /// use axiom_spinlock::SpinLock;
-    ///
-    /// let lock = SpinLock::new(123);
-    /// assert_eq!(*lock.lock(), 123);
-    /// ```
+/// An explicit acquire/release session, returned by [`SpinLock::enter`].
+///
+/// It holds the lock like a [`SpinGuard`] but has no `Deref`: the data is
+/// reached through [`data`](Self::data), and the session ends with an explicit
+/// [`leave`](Self::leave). Dropping it without `leave` still releases the lock,
+/// as a backstop. Being a plain named type, it is convenient to store in a
+/// struct across FFI callbacks (begin in one callback, finish in another).
+pub struct SpinSession<'a, T> {
+    guard: SpinGuard<'a, T>,
+}
+
+impl<T> SpinSession<'_, T> {
+    /// Returns exclusive access to the data for as long as the session is borrowed.
     #[inline(always)]
-    pub const fn new(data: T) -> Self {
-        SpinLock {
-            data: UnsafeCell::new(data),
-            locked: AtomicBool::new(false),
-        }
+    pub fn data(&mut self) -> &mut T {
+        &mut self.guard
     }
 
-    /// Acquires the lock, spinning until it becomes available.
-    ///
-    /// Uses an exponential [`BackOff`] to reduce contention.
-    /// Returns a [`SpinGuard`] which automatically releases the lock on drop.
+    /// Ends the session, releasing the lock.
     #[inline]
-    pub fn lock(&self) -> SpinGuard<'_, T> {
-        let backoff = BackOff::new();
-        while self.locked.swap(true, Acquire) {
-            // Acquire is sufficient here since swap ensures visibility of writes
-            backoff.wait();
-        }
-
-        SpinGuard { guard: self }
+    pub fn leave(self) {
+        drop(self);
     }
+}
 
-    /// Unsafely releases the lock manually.
-    ///
-    /// # Safety
-    /// - Only call this if you *own* the lock.
-    /// - Misuse can cause data races or UB.
-    #[inline]
-    pub unsafe fn unlock(&self) {
-        self.locked.store(false, Release);
+/// A guard that keeps its [`SpinLock`] alive through an `Arc`, returned by
+/// [`SpinLock::lock_arc`] and [`SpinLock::lock_weak`] (requires the `alloc`
+/// feature).
+///
+/// It has no lifetime, so it can be stored in structs or moved into a spawned
+/// thread; the lock is released when the guard is dropped.
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+pub struct ArcSpinGuard<T> {
+    lock: alloc::sync::Arc<SpinLock<T>>,
+    /// Opts out of the auto `Sync`, which would only require `T: Send`.
+    _not_sync: core::marker::PhantomData<core::cell::Cell<()>>,
+}
+
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+impl<T> ArcSpinGuard<T> {
+    /// Returns the lock this guard holds.
+    #[inline(always)]
+    pub fn lock(this: &Self) -> &alloc::sync::Arc<SpinLock<T>> {
+        &this.lock
     }
+}
 
-    /// Attempts to acquire the lock without blocking.
-    ///
-    /// Returns `Some(SpinGuard)` if the lock was free, or `None` otherwise.
-    #[inline]
-    pub fn try_lock(&self) -> Option<SpinGuard<'_, T>> {
-        if !self.locked.swap(true, Acquire) {
-            Some(SpinGuard { guard: self })
-        } else {
-            None
-        }
+// Safety: sharing the guard shares `&T`, exactly like sharing `&T` itself.
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+unsafe impl<T: Send + Sync> Sync for ArcSpinGuard<T> {}
+
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+impl<T> Deref for ArcSpinGuard<T> {
+    type Target = T;
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        // Safety: the guard holds the lock.
+        unsafe { &*self.lock.data.get() }
     }
+}
 
-    /// Checks whether the lock is currently held.
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+impl<T> DerefMut for ArcSpinGuard<T> {
     #[inline(always)]
-    pub fn is_locked(&self) -> bool {
-        self.locked.load(Acquire)
+    fn deref_mut(&mut self) -> &mut T {
+        // Safety: the guard holds the lock, and `&mut self` makes this access unique.
+        unsafe { &mut *self.lock.data.get() }
     }
+}
 
-    /// Tries to acquire the lock within a fixed number of spin attempts.
-    ///
-    /// Returns `Some(SpinGuard)` if successful, otherwise `None` after the given number of spins.
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+impl<T> Drop for ArcSpinGuard<T> {
     #[inline]
-    pub fn try_lock_for(&self, spins: usize) -> Option<SpinGuard<'_, T>> {
-        let backoff = BackOff::new();
-        for _ in 0..spins {
-            if !self.locked.swap(true, Acquire) {
-                return Some(SpinGuard { guard: self });
-            }
-            backoff.wait();
-        }
-        None
+    fn drop(&mut self) {
+        self.lock.release();
     }
+}
 
-    /// Runs a closure with exclusive access to the data.
-    ///
-    /// This is a convenience wrapper around [`lock()`] that automatically releases
-    /// the lock when the closure returns.
-    ///
-    /// # Example
-    /// ```
-    /// use axiom_spinlock::SpinLock;
-    /// let lock = SpinLock::new(0i32);
-    /// lock.with_lock(|data| {
-    ///     *data += 1;
-    /// });
-    /// ```
-    #[inline]
-    pub fn with_lock<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
-        let mut guard = self.lock();
-        f(&mut *guard)
+/// A guard that owns its [`SpinLock`], returned by [`SpinLock::into_guarded`].
+///
+/// Ownership of the lock is what grants access, so the guard never touches the
+/// lock flag and dropping it simply drops the lock and its data.
+pub struct OwnedSpinGuard<T> {
+    lock: SpinLock<T>,
+}
+
+impl<T> OwnedSpinGuard<T> {
+    /// Consumes the guard and returns the protected data.
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.lock.into_inner()
+    }
+
+    /// Consumes the guard and returns the lock, ready to be shared again.
+    #[inline(always)]
+    pub fn into_lock(self) -> SpinLock<T> {
+        self.lock
     }
 }
 
-impl<T> Deref for SpinGuard<'_, T> {
+impl<T> Deref for OwnedSpinGuard<T> {
     type Target = T;
     #[inline(always)]
     fn deref(&self) -> &T {
-        unsafe { &*(self.guard.data.get()) }
+        // Safety: the guard owns the lock, so no other reference to the data exists.
+        unsafe { &*self.lock.data.get() }
     }
 }
 
-impl<T> DerefMut for SpinGuard<'_, T> {
+impl<T> DerefMut for OwnedSpinGuard<T> {
     #[inline(always)]
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        unsafe { &mut *self.guard.data.get() }
+    fn deref_mut(&mut self) -> &mut T {
+        self.lock.get_mut()
     }
 }
 
-// Safety: SpinLock enforces mutual exclusion via atomic operations.
-unsafe impl<T: Send> Send for SpinLock<T> {}
-unsafe impl<T: Send> Sync for SpinLock<T> {}
+/// Why [`SpinLock::try_lock_err`] did not acquire the lock.
+///
+/// `SpinLock` has no poisoning, so contention is currently the only cause. The
+/// enum is `#[non_exhaustive]` so that a `Poisoned` variant can be added once
+/// poisoning support lands without breaking exhaustive matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryLockError {
+    /// Another holder has the lock; acquiring it would have required waiting.
+    WouldBlock,
+}
 
+impl fmt::Display for TryLockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryLockError::WouldBlock => {
+                f.write_str("try_lock failed because the operation would block")
+            }
+        }
+    }
+}
 
-#[cfg(test)]
-mod test{
-    #[test]
-    fn test_basic_lock_unlock() {
-        use crate::SpinLock;
+#[cfg(feature = "std")]
+impl std::error::Error for TryLockError {}
 
-        let lock = SpinLock::new(10);
+/// A guard that releases the [`SpinLock`] when dropped.
+///
+/// This is returned from [`SpinLock::lock`] and implements [`Deref`] and [`DerefMut`]
+/// to access the underlying data.
+pub struct SpinGuard<'a, T> {
+    guard: &'a SpinLock<T>,
+    /// Backoff rounds performed before this guard's acquisition succeeded.
+    #[cfg(feature = "metrics")]
+    spins: u32,
+    /// Lock generation this guard's hold belongs to.
+    #[cfg(feature = "debug-guard-token")]
+    token: u32,
+}
 
+impl<'a, T> SpinGuard<'a, T> {
+    #[inline(always)]
+    fn new(guard: &'a SpinLock<T>, _spins: u32) -> Self {
+        #[cfg(feature = "deadlock-detection")]
         {
-            let mut guard = lock.lock();
-            *guard += 5;
-            assert_eq!(*guard, 15);
-        } // guard dropped here, automatically unlocks
+            *guard.holder() = Some(std::thread::current().id());
+        }
+        SpinGuard {
+            guard,
+            #[cfg(feature = "metrics")]
+            spins: _spins,
+            // Written before the previous holder's unlock, read after our Acquire.
+            #[cfg(feature = "debug-guard-token")]
+            token: guard.generation.load(core::sync::atomic::Ordering::Relaxed),
+        }
+    }
 
-        assert!(!lock.is_locked(), "Lock should be released after guard drop");
+    /// Returns how many backoff rounds the acquisition of this guard took.
+    ///
+    /// `0` means the lock was acquired on the first attempt. The count is stored
+    /// in the guard itself, so reading it is free and involves no shared state;
+    /// it is a per-acquisition signal of how contended the lock was, e.g. to
+    /// decide whether a hot lock should be sharded.
+    ///
+    /// Only available with the `metrics` feature, so the default fast path does
+    /// not carry the counter.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let lock = SpinLock::new(0);
+    /// assert_eq!(lock.lock().spin_count(), 0);
+    /// ```
+    #[cfg(feature = "metrics")]
+    #[inline(always)]
+    pub fn spin_count(&self) -> u32 {
+        self.spins
     }
+}
 
-    #[cfg(feature = "std")]
-    #[test]
-    fn test_concurrent_access() {
-        use crate::SpinLock;
-        use std::sync::Arc;
-        use std::thread;
+impl<'a, T> SpinGuard<'a, T> {
+    /// Leaks the guard, keeping the lock held forever, and returns the data.
+    ///
+    /// The lock is never released (unless someone calls the unsafe
+    /// [`SpinLock::unlock`]), so this is mainly useful for data that should be
+    /// initialized once and then owned for the rest of the program.
+    ///
+    /// This is an associated function, called as `SpinGuard::leak(guard)`, so it
+    /// does not shadow methods of `T`.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::{spinlock::SpinGuard, SpinLock};
+    ///
+    /// static CONFIG: SpinLock<u32> = SpinLock::new(0);
+    ///
+    /// let config: &'static mut u32 = SpinGuard::leak(CONFIG.lock());
+    /// *config = 8;
+    /// assert!(CONFIG.is_locked());
+    /// ```
+    #[inline]
+    pub fn leak(this: Self) -> &'a mut T {
+        let lock = this.guard;
+        core::mem::forget(this);
+        // Safety: the forgotten guard's lock stays held, so this is the only
+        // access to the data for as long as `'a`.
+        unsafe { &mut *lock.data.get() }
+    }
 
-        let lock = Arc::new(SpinLock::new(0usize));
-        let mut handles = vec![];
+    /// Briefly releases the lock so that a waiting thread can take it, then
+    /// reacquires it.
+    ///
+    /// `SpinLock` is unfair and keeps no waiter queue, so this cannot hand the
+    /// lock to a specific waiter: it releases, gives other threads a window
+    /// (`std::thread::yield_now` under `std`, a single spin hint otherwise) and
+    /// spins to reacquire like [`SpinLock::lock`]. Use it at a safe point in
+    /// a long critical section to let starved waiters in.
+    ///
+    /// **Other threads may lock and modify the data while bumping**, so the
+    /// data's invariants must hold at the bump point, and anything read before
+    /// the call must be re-read after it.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let queue = SpinLock::new(vec![1, 2, 3]);
+    /// let mut guard = queue.lock();
+    /// while let Some(_item) = guard.pop() {
+    ///     // Each item leaves the vector consistent, so waiters may step in.
+    ///     guard.bump();
+    /// }
+    /// ```
+    #[inline]
+    pub fn bump(&mut self) {
+        let lock = self.guard;
+        lock.release();
 
-        for _ in 0..8 {
-            let lock_cloned = lock.clone();
-            handles.push(thread::spawn(move || {
-                for _ in 0..10_000 {
-                    let mut guard = lock_cloned.lock();
-                    *guard += 1;
-                }
-            }));
-        }
+        #[cfg(feature = "std")]
+        std::thread::yield_now();
+        #[cfg(not(feature = "std"))]
+        core::hint::spin_loop();
 
-        for h in handles {
-            h.join().unwrap();
+        // `self` takes over the fresh hold; the new guard itself is never dropped.
+        let fresh = core::mem::ManuallyDrop::new(lock.lock());
+        #[cfg(feature = "debug-guard-token")]
+        {
+            self.token = fresh.token;
         }
+        let _ = fresh;
+    }
 
-        let final_value = *lock.lock();
-        assert_eq!(final_value, 8 * 10_000, "Counter should match total increments");
+    /// Releases the lock and returns the lock itself, for primitives that wait
+    /// outside the critical section and reacquire afterwards.
+    #[inline]
+    pub(crate) fn unlocked(this: Self) -> &'a SpinLock<T> {
+        let lock = this.guard;
+        drop(this);
+        lock
     }
+}
 
-    #[test]
-    fn test_try_lock_for_behavior() {
-        use crate::SpinLock;
+impl<T: Clone> SpinGuard<'_, T> {
+    /// Clones the data while the lock is still held.
+    ///
+    /// Makes the copy-then-release pattern explicit: take the snapshot, drop
+    /// the guard, and do the slow work on the copy outside the critical
+    /// section.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let names = SpinLock::new(vec!["a", "b"]);
+    /// let guard = names.lock();
+    /// let snapshot = guard.clone_data();
+    /// drop(guard);
+    /// assert_eq!(snapshot, ["a", "b"]);
+    /// ```
+    #[inline]
+    pub fn clone_data(&self) -> T {
+        T::clone(self)
+    }
+}
 
-        let lock = SpinLock::new(42);
+impl<'a, T> Drop for SpinGuard<'a, T> {
+    #[inline]
+    fn drop(&mut self) {
+        #[cfg(feature = "debug-guard-token")]
+        if self.token != self.guard.generation.load(core::sync::atomic::Ordering::Relaxed) {
+            // The hold this guard stood for was already released (`unlock` after a
+            // `forget`/`ManuallyDrop`); releasing again would free someone else's.
+            debug_assert!(
+                false,
+                "stale SpinGuard dropped: the lock at {:p} was released since this guard \
+                 acquired it (manual unlock after forget?)",
+                self.guard,
+            );
+            return;
+        }
+        self.guard.release()
+    }
+}
 
-        // Acquire lock once
-        let _guard = lock.lock();
+impl<T> SpinLock<T> {
+    /// Byte offset of the lock flag (an `AtomicBool`) from the start of the lock.
+    ///
+    /// The data is always at offset `0`; see the [layout](SpinLock#layout) notes.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// assert_eq!(SpinLock::<u32>::LOCKED_OFFSET, 4);
+    /// assert_eq!(SpinLock::<[u8; 3]>::LOCKED_OFFSET, 3);
+    /// ```
+    pub const LOCKED_OFFSET: usize = core::mem::offset_of!(Self, raw);
+
+    /// Creates a new [`SpinLock`] wrapping the given data.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let lock = SpinLock::new(123);
+    /// assert_eq!(*lock.lock(), 123);
+    /// ```
+    #[inline(always)]
+    pub const fn new(data: T) -> Self {
+        SpinLock {
+            data: UnsafeCell::new(data),
+            raw: RawSpinLock::new(),
+            config: 0,
+            #[cfg(target_has_atomic = "32")]
+            backoff_start: core::sync::atomic::AtomicU32::new(BackOff::START_VALUE),
+            #[cfg(not(target_has_atomic = "32"))]
+            backoff_start: BackOff::START_VALUE,
+            #[cfg(feature = "parking")]
+            parking: Parking::new(),
+            #[cfg(feature = "contention-hint")]
+            contention: core::sync::atomic::AtomicU8::new(0),
+            #[cfg(feature = "adaptive")]
+            spin_ewma: core::sync::atomic::AtomicU32::new(0),
+            #[cfg(feature = "deadlock-detection")]
+            holder: std::sync::Mutex::new(None),
+            #[cfg(feature = "profiling")]
+            site: None,
+            #[cfg(feature = "debug-guard-token")]
+            generation: core::sync::atomic::AtomicU32::new(0),
+        }
+    }
+
+    /// Creates a new [`SpinLock`] whose [`lock`](Self::lock) retries with a
+    /// single [`spin_loop`](core::hint::spin_loop) between attempts, never
+    /// escalating.
+    ///
+    /// For the very shortest critical sections (a few instructions) on locks that
+    /// are almost always free: the default exponential [`BackOff`] starts at
+    /// [`BackOff::START_VALUE`] pauses, far longer than such a holder takes to
+    /// release, so a waiter keeps missing the release it was waiting for. A
+    /// constant one-pause backoff notices it within a few cycles instead. The
+    /// price is that waiters hammer the lock's cache line and never yield, which
+    /// hurts under sustained contention or long holds: it trades aggregate
+    /// throughput for per-waiter wakeup latency (in the bundled benchmark, with
+    /// several threads incrementing back to back, the default lock has the
+    /// higher throughput). Measure your workload first
+    /// (`cargo bench --bench contention -- low_latency`).
+    ///
+    /// Only `lock` (and the methods built on it, such as
+    /// [`with_lock`](Self::with_lock)) use this mode; the `try_*` variants keep
+    /// their documented backoff.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// static HITS: SpinLock<u64> = SpinLock::new_low_latency(0);
+    ///
+    /// *HITS.lock() += 1;
+    /// assert_eq!(*HITS.lock(), 1);
+    /// ```
+    #[inline(always)]
+    pub const fn new_low_latency(data: T) -> Self {
+        let mut lock = Self::new(data);
+        lock.config |= CONFIG_LOW_LATENCY;
+        lock
+    }
+
+    /// Creates a new [`SpinLock`] whose [`lock`](Self::lock) calls are counted
+    /// under `name` in the [profiling](crate::profiling) registry (requires the
+    /// `profiling` feature).
+    ///
+    /// Registering walks a short lock-free list and, for a new name, allocates
+    /// its site once; locks sharing a name aggregate into one site.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::{profiling, SpinLock};
+    ///
+    /// let lock = SpinLock::with_name("doc.with_name", 0);
+    /// *lock.lock() += 1;
+    /// assert!(profiling::report().iter().any(|s| s.name == "doc.with_name"));
+    /// ```
+    #[cfg(feature = "profiling")]
+    #[inline]
+    pub fn with_name(name: &'static str, data: T) -> Self {
+        let mut lock = Self::new(data);
+        lock.site = Some(crate::profiling::LockSite::register(name));
+        lock
+    }
+
+    /// Creates a new [`SpinLock`] whose guards release the lock with a `SeqCst`
+    /// store instead of `Release`.
+    ///
+    /// Acquisition is unchanged (`Acquire`). The default `Release` store is all
+    /// the data under the lock ever needs: every later acquisition synchronizes
+    /// with it. `SeqCst` additionally puts the release in the single total
+    /// order of `SeqCst` operations, for algorithms that combine the lock with
+    /// `SeqCst` accesses or fences on other variables, such as a
+    /// store-buffering handshake (release the lock, then check another
+    /// thread's flag) where `Release` alone lets both threads miss each other.
+    ///
+    /// # Ordering combinations
+    /// - Default `Release` release + `Acquire` acquisition: sound for all data
+    ///   accessed under the lock.
+    /// - `SeqCst` release + a `SeqCst` load of another variable afterwards, on
+    ///   one side; a `SeqCst` store (or any store followed by a `SeqCst` fence)
+    ///   then a look at the lock ([`is_locked`](Self::is_locked)) on the other:
+    ///   at least one side observes the other's write. With `Release` this
+    ///   guarantee does not hold.
+    /// - Taking the lock is *not* a `SeqCst` operation either way; code that
+    ///   needs ordering after acquiring must use its own `SeqCst` accesses or
+    ///   a `SeqCst` fence.
+    ///
+    /// The stronger store costs a full barrier per release on most targets
+    /// (`xchg` on x86, `stlr` plus ordering constraints on aarch64).
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// static PUBLISHED: SpinLock<u32> = SpinLock::new_seqcst(0);
+    ///
+    /// *PUBLISHED.lock() = 7; // released with a `SeqCst` store
+    /// assert_eq!(*PUBLISHED.lock(), 7);
+    /// ```
+    #[inline(always)]
+    pub const fn new_seqcst(data: T) -> Self {
+        let mut lock = Self::new(data);
+        lock.config |= CONFIG_SEQCST_RELEASE;
+        lock
+    }
+
+    /// Creates a new [`SpinLock`] whose contended [`lock`](Self::lock) starts
+    /// backing off at `start` spins instead of [`BackOff::START_VALUE`].
+    ///
+    /// Tunes contention recovery per lock: a small `start` retries quickly on a
+    /// lock that is rarely fought over, a large one keeps a known-hot lock's
+    /// waiters off its cache line from the first round. Rounds then escalate
+    /// as usual (see [`BackOff::new_with`]); with `contention-hint` the
+    /// recent-contention shift applies on top of `start`. Only `lock` and the
+    /// methods built on it use this value; the `try_*` variants keep their
+    /// documented backoff.
+    ///
+    /// `start` is clamped to `1..=`[`BackOff::MAX_SPIN`]: `0` would never
+    /// escalate (it doubles to `0`), so it is treated as `1`, and rounds never
+    /// spin longer than `MAX_SPIN` anyway. A `start` above
+    /// [`BackOff::YIELD_THRESHOLD`] makes every contended round yield the
+    /// thread after spinning (with `std`), like a default [`BackOff`] does once
+    /// it escalates that far.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// static HOT: SpinLock<u64> = SpinLock::new_with_backoff(0, 512);
+    ///
+    /// *HOT.lock() += 1;
+    /// assert_eq!(*HOT.lock(), 1);
+    /// ```
+    #[inline(always)]
+    pub const fn new_with_backoff(data: T, start: u32) -> Self {
+        let mut lock = Self::new(data);
+        let start = if start == 0 {
+            1
+        } else if start > BackOff::MAX_SPIN {
+            BackOff::MAX_SPIN
+        } else {
+            start
+        };
+        #[cfg(target_has_atomic = "32")]
+        {
+            lock.backoff_start = core::sync::atomic::AtomicU32::new(start);
+        }
+        #[cfg(not(target_has_atomic = "32"))]
+        {
+            lock.backoff_start = start;
+        }
+        lock
+    }
+
+    /// Creates a new [`SpinLock`] whose [`lock`](Self::lock) learns its starting
+    /// backoff from recent acquisitions.
+    ///
+    /// The lock keeps the spin count its contended `lock` calls start at
+    /// (initially [`BackOff::START_VALUE`], like [`new`](Self::new)) and retunes
+    /// it after every `lock`:
+    /// - an acquisition that had to back off keeps the escalation it reached:
+    ///   the start doubles once per backoff round, up to
+    ///   [`MAX_ADAPTIVE_BACKOFF_START`];
+    /// - an uncontended acquisition [`relax`](BackOff::relax)es it (halves it,
+    ///   down to 1).
+    ///
+    /// So a lock that stays hot backs off harder from the first round, and one
+    /// that cools down goes back to retrying quickly. The value is shared by all
+    /// threads through Relaxed loads and stores; racing updates may overwrite
+    /// each other, which only makes the adaptation approximate. Unrelated to the
+    /// `adaptive` feature, which learns when `lock` starts yielding; both can be
+    /// combined. Only available on targets with 32-bit atomics.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// static QUEUE: SpinLock<Vec<u32>> = SpinLock::new_adaptive(Vec::new());
+    ///
+    /// QUEUE.lock().push(1);
+    /// assert_eq!(QUEUE.lock().len(), 1);
+    /// ```
+    #[cfg(target_has_atomic = "32")]
+    #[inline(always)]
+    pub const fn new_adaptive(data: T) -> Self {
+        let mut lock = Self::new(data);
+        lock.config |= CONFIG_ADAPT_BACKOFF;
+        lock
+    }
+
+    /// Creates a new [`SpinLock`] already wrapped in an [`Arc`](alloc::sync::Arc),
+    /// ready to be shared between threads (requires the `alloc` feature).
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    /// use std::thread;
+    ///
+    /// let counter = SpinLock::new_arc(0);
+    /// let handles: Vec<_> = (0..4)
+    ///     .map(|_| {
+    ///         let counter = counter.clone();
+    ///         thread::spawn(move || *counter.lock() += 1)
+    ///     })
+    ///     .collect();
+    /// for h in handles {
+    ///     h.join().unwrap();
+    /// }
+    /// assert_eq!(*counter.lock(), 4);
+    /// ```
+    #[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+    #[inline]
+    pub fn new_arc(data: T) -> alloc::sync::Arc<Self> {
+        alloc::sync::Arc::new(Self::new(data))
+    }
+
+    /// Acquires the lock, like [`lock`](Self::lock), and returns an
+    /// [`ArcSpinGuard`] that owns a clone of the `Arc` instead of borrowing it.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    /// use std::thread;
+    ///
+    /// let log = SpinLock::new_arc(Vec::new());
+    /// let mut guard = log.lock_arc();
+    /// // The guard is 'static: it can travel to another thread.
+    /// thread::spawn(move || guard.push("from the worker")).join().unwrap();
+    /// assert_eq!(*log.lock(), ["from the worker"]);
+    /// ```
+    #[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+    #[inline]
+    #[cfg_attr(feature = "deadlock-detection", track_caller)]
+    pub fn lock_arc(self: &alloc::sync::Arc<Self>) -> ArcSpinGuard<T> {
+        // The hold is taken over by the `ArcSpinGuard`, which releases it on drop.
+        SpinGuard::leak(self.lock());
+        ArcSpinGuard {
+            lock: alloc::sync::Arc::clone(self),
+            _not_sync: core::marker::PhantomData,
+        }
+    }
+
+    /// Upgrades `weak` and locks the lock, or returns `None` if the lock (and
+    /// its data) has already been dropped.
+    ///
+    /// For caches and observers that hold a [`Weak`](alloc::sync::Weak) to
+    /// shared state: `None` means the data is gone, not that the lock was busy;
+    /// a live lock is waited for like [`lock_arc`](Self::lock_arc).
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    /// use std::sync::Arc;
+    ///
+    /// let subject = SpinLock::new_arc(0);
+    /// let observer = Arc::downgrade(&subject);
+    ///
+    /// *SpinLock::lock_weak(&observer).unwrap() += 1;
+    /// drop(subject);
+    /// assert!(SpinLock::lock_weak(&observer).is_none());
+    /// ```
+    #[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+    #[inline]
+    #[cfg_attr(feature = "deadlock-detection", track_caller)]
+    pub fn lock_weak(weak: &alloc::sync::Weak<Self>) -> Option<ArcSpinGuard<T>> {
+        weak.upgrade().map(|lock| lock.lock_arc())
+    }
+
+    /// Creates a new [`SpinLock`] that starts in the held state.
+    ///
+    /// Meant for initialization handshakes: every [`lock`](Self::lock) blocks
+    /// until the initializing thread finishes setup and releases the lock. The
+    /// initializer takes ownership of that initial hold with the unsafe
+    /// [`assume_locked`](Self::assume_locked), which returns a regular guard, so
+    /// the release can't be forgotten on early return or panic.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// static CONFIG: SpinLock<u32> = SpinLock::new_locked(0);
+    ///
+    /// // Initializing thread (the only one that owns the initial hold):
+    /// // Safety: nobody else called `assume_locked` on CONFIG.
+    /// let mut setup = unsafe { CONFIG.assume_locked() };
+    /// *setup = 8;
+    /// drop(setup);
+    ///
+    /// // Everyone else:
+    /// assert_eq!(*CONFIG.lock(), 8);
+    /// ```
+    #[inline(always)]
+    pub const fn new_locked(data: T) -> Self {
+        let mut lock = Self::new(data);
+        lock.raw = RawSpinLock::new_locked();
+        lock
+    }
+
+    /// Initializes an unlocked [`SpinLock`] holding `data` in place at `ptr`.
+    ///
+    /// For locks living in raw memory, such as an `mmap`ed segment shared
+    /// between processes. The lock's state is just its data and an atomic flag —
+    /// no process-local pointers — so any process mapping the segment can lock
+    /// it once one of them has run `init_at`. Guards borrow the lock, and
+    /// therefore must not outlive the mapping (nor be used after it is unmapped
+    /// by another route). The `parking` and `deadlock-detection` features add
+    /// process-local state (OS mutexes, thread ids) and must be off for
+    /// inter-process use; `T` itself must be position-independent, too.
+    ///
+    /// # Safety
+    /// - `ptr` must be valid for writes and aligned for `SpinLock<T>`.
+    /// - No other thread or process may access the memory until this returns.
+    /// - Any value previously at `ptr` is overwritten without being dropped.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    /// use std::mem::MaybeUninit;
+    ///
+    /// let mut region = MaybeUninit::<SpinLock<u64>>::uninit();
+    /// unsafe { SpinLock::init_at(region.as_mut_ptr(), 5) };
+    /// let lock = unsafe { region.assume_init_ref() };
+    /// assert_eq!(*lock.lock(), 5);
+    /// ```
+    #[inline]
+    pub unsafe fn init_at(ptr: *mut SpinLock<T>, data: T) {
+        ptr.write(Self::new(data));
+    }
+
+    /// Returns a guard for a hold the caller already owns, without acquiring.
+    ///
+    /// The counterpart of [`new_locked`](Self::new_locked), and of a guard that was
+    /// leaked with [`try_get_mut`](Self::try_get_mut) or [`SpinGuard::leak`]:
+    /// dropping the returned guard releases the lock.
+    ///
+    /// # Safety
+    /// The lock must currently be held, and the caller must own that hold: no
+    /// other guard or `&mut T` for it may be alive, and no one else may release it
+    /// or call `assume_locked` for it.
+    #[inline]
+    pub unsafe fn assume_locked(&self) -> SpinGuard<'_, T> {
+        debug_assert!(self.is_locked(), "assume_locked called on an unlocked SpinLock");
+        SpinGuard::new(self, 0)
+    }
+
+    /// Returns a shared reference to the data without touching the lock, for a
+    /// single-writer initialization phase.
+    ///
+    /// Phased startup often looks like: one thread builds the data, reads it
+    /// back while wiring things up, and only then publishes the lock to other
+    /// threads, which use [`lock`](Self::lock) from there on. Reads in the first
+    /// phase need no atomics, because nothing can be writing concurrently; this
+    /// method makes them explicit.
+    ///
+    /// The boundary is the reference's lifetime: while it is alive the data is
+    /// frozen for everyone, the initializing thread included.
+    ///
+    /// # Safety
+    /// For as long as the returned reference is alive:
+    /// - no guard, [`OwnedSpinGuard`] or `&mut T` for this lock may exist or be
+    ///   created, on any thread (a held lock may be read only if its holder is
+    ///   not writing, e.g. a [`new_locked`](Self::new_locked) hold that stays
+    ///   idle);
+    /// - every write to the data must happen-before this call (e.g. it was made
+    ///   by this thread, or before the thread was spawned, or under a lock this
+    ///   thread has since acquired).
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    /// use std::thread;
+    ///
+    /// let routes = SpinLock::new(vec![]);
+    /// routes.lock().extend(["/", "/health"]);
+    ///
+    /// // Init phase: this thread is the only one that knows about `routes`.
+    /// let count = unsafe { routes.assume_init_ref() }.len();
+    /// assert_eq!(count, 2);
+    ///
+    /// // Shared phase: synchronized access only.
+    /// thread::scope(|s| {
+    ///     s.spawn(|| routes.lock().push("/metrics"));
+    /// });
+    /// assert_eq!(routes.lock().len(), 3);
+    /// ```
+    #[inline(always)]
+    pub unsafe fn assume_init_ref(&self) -> &T {
+        &*self.data.get()
+    }
+
+    /// Consumes the lock and returns the protected data.
+    ///
+    /// No locking is needed: owning the lock proves nobody else can hold it.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let lock = SpinLock::new(String::from("axiom"));
+    /// assert_eq!(lock.into_inner(), "axiom");
+    /// ```
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+
+    /// Returns a mutable reference to the data without locking.
+    ///
+    /// The `&mut self` borrow statically guarantees exclusive access.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let mut lock = SpinLock::new(1);
+    /// *lock.get_mut() += 1;
+    /// assert_eq!(*lock.lock(), 2);
+    /// ```
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> &mut T {
+        self.data.get_mut()
+    }
+
+    /// Moves the lock into an [`OwnedSpinGuard`], an owning guard with no lifetime.
+    ///
+    /// A [`SpinGuard`] borrows its lock, so a function that creates a lock by
+    /// value cannot return a guard for it. The owned guard holds the whole lock
+    /// instead: owning it *is* exclusive access, so nothing is acquired or
+    /// released. Finish with [`OwnedSpinGuard::into_inner`], or turn it back into
+    /// a shareable lock with [`OwnedSpinGuard::into_lock`]. To share the data
+    /// while handing out `'static` guards, put the lock in an `Arc` instead.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    /// use axiom_spinlock::spinlock::OwnedSpinGuard;
+    ///
+    /// fn builder() -> OwnedSpinGuard<Vec<u8>> {
+    ///     let lock = SpinLock::new(Vec::new());
+    ///     let mut guard = lock.into_guarded();
+    ///     guard.push(1);
+    ///     guard
+    /// }
+    ///
+    /// let mut guard = builder();
+    /// guard.push(2);
+    /// assert_eq!(guard.into_inner(), [1, 2]);
+    /// ```
+    #[inline(always)]
+    pub fn into_guarded(self) -> OwnedSpinGuard<T> {
+        OwnedSpinGuard { lock: self }
+    }
+
+    #[cfg(feature = "deadlock-detection")]
+    #[inline]
+    fn holder(&self) -> std::sync::MutexGuard<'_, Option<std::thread::ThreadId>> {
+        self.holder
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Panics if the calling thread already holds this lock.
+    ///
+    /// Only the holder itself ever records or clears its own id, so observing the
+    /// current thread's id here means it really does hold the lock.
+    #[cfg(feature = "deadlock-detection")]
+    #[track_caller]
+    fn assert_not_reentrant(&self) {
+        if *self.holder() == Some(std::thread::current().id()) {
+            panic!(
+                "deadlock detected: thread {:?} tried to lock the SpinLock at {:p} \
+                 that it already holds (SpinLock is not reentrant)",
+                std::thread::current().id(),
+                self,
+            );
+        }
+    }
+
+    /// The [`BackOff`] a contended [`lock`](Self::lock) waits with: starting at
+    /// the lock's configured spin count, seeded by the `contention-hint` and
+    /// tuned by the `adaptive` features.
+    #[inline(always)]
+    fn lock_backoff(&self) -> BackOff {
+        #[cfg(feature = "contention-hint")]
+        let start = self.backoff_start().saturating_mul(1 << self.contention_hint());
+        #[cfg(not(feature = "contention-hint"))]
+        let start = self.backoff_start();
+
+        #[cfg(feature = "adaptive")]
+        {
+            // Round `r` starts at `start << r` spins; yield past the budget,
+            // but never later than a default `BackOff` would, so a start
+            // above the default threshold yields from its first round.
+            let max_shift = (BackOff::YIELD_THRESHOLD / BackOff::START_VALUE).trailing_zeros();
+            let shift = self.adaptive_spin_budget().min(max_shift);
+            let threshold = start.saturating_mul(1 << shift).min(BackOff::YIELD_THRESHOLD);
+            BackOff::new_with_threshold(start, threshold)
+        }
+        #[cfg(not(feature = "adaptive"))]
+        BackOff::new_with(start)
+    }
+
+    #[inline(always)]
+    fn backoff_start(&self) -> u32 {
+        #[cfg(target_has_atomic = "32")]
+        return self.backoff_start.load(core::sync::atomic::Ordering::Relaxed);
+        #[cfg(not(target_has_atomic = "32"))]
+        self.backoff_start
+    }
+
+    /// Retunes the starting backoff of a [`new_adaptive`](Self::new_adaptive)
+    /// lock after a `lock` that took `rounds` backoff rounds.
+    #[cfg(target_has_atomic = "32")]
+    #[inline]
+    fn adapt_backoff_start(&self, rounds: u32) {
+        let start = self.backoff_start();
+        let next = if rounds == 0 {
+            let relaxed = BackOff::new_with(start);
+            relaxed.relax();
+            relaxed.current().max(1)
+        } else {
+            let shift = rounds.min(MAX_ADAPTIVE_BACKOFF_START.trailing_zeros());
+            start.saturating_mul(1 << shift).min(MAX_ADAPTIVE_BACKOFF_START)
+        };
+        if next != start {
+            self.backoff_start.store(next, core::sync::atomic::Ordering::Relaxed);
+        }
+    }
+
+    /// How hot this lock has been recently, from `0` (cold) to
+    /// [`MAX_CONTENTION_HINT`] (only available with `contention-hint`).
+    ///
+    /// Every contended [`lock`](Self::lock) raises it by one and every
+    /// uncontended one lowers it by one. A contended `lock` starts its backoff at
+    /// `BackOff::START_VALUE << hint`, so a lock that keeps being fought over
+    /// backs off harder from the first round, while a cold lock starts gently.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let lock = SpinLock::new(());
+    /// drop(lock.lock());
+    /// assert_eq!(lock.contention_hint(), 0);
+    /// ```
+    #[cfg(feature = "contention-hint")]
+    #[inline]
+    pub fn contention_hint(&self) -> u8 {
+        self.contention.load(core::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Moves the contention hint one step towards hot (`rounds > 0`) or cold.
+    #[cfg(feature = "contention-hint")]
+    #[inline]
+    fn record_contention(&self, rounds: u32) {
+        use core::sync::atomic::Ordering::Relaxed;
+
+        let hint = self.contention.load(Relaxed);
+        if rounds > 0 && hint < MAX_CONTENTION_HINT {
+            self.contention.store(hint + 1, Relaxed);
+        } else if rounds == 0 && hint > 0 {
+            self.contention.store(hint - 1, Relaxed);
+        }
+    }
+
+    /// Backoff rounds [`lock`](Self::lock) currently spins before yielding, learned
+    /// from recent acquisitions (only available with `adaptive`).
+    ///
+    /// This is `2 · avg + 1` for the moving average `avg` described in the
+    /// [module docs](crate::spinlock#adaptive-spinning); a fresh lock starts at 1.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let lock = SpinLock::new(());
+    /// drop(lock.lock()); // uncontended: the average stays at zero
+    /// assert_eq!(lock.adaptive_spin_budget(), 1);
+    /// ```
+    #[cfg(feature = "adaptive")]
+    #[inline]
+    pub fn adaptive_spin_budget(&self) -> u32 {
+        let scaled = self.spin_ewma.load(core::sync::atomic::Ordering::Relaxed);
+        (scaled / EWMA_SCALE).saturating_mul(2).saturating_add(1)
+    }
+
+    /// Folds one acquisition's backoff `rounds` into the adaptive average.
+    #[cfg(feature = "adaptive")]
+    #[inline]
+    fn record_spin_rounds(&self, rounds: u32) {
+        use core::sync::atomic::Ordering::Relaxed;
+
+        let old = self.spin_ewma.load(Relaxed);
+        // scaled' = scaled - scaled/8 + rounds, i.e. avg' = avg + (rounds - avg)/8.
+        let new = old - old / EWMA_SCALE + rounds.min(u32::MAX / EWMA_SCALE);
+        if new != old {
+            self.spin_ewma.store(new, Relaxed);
+        }
+    }
+
+    /// Asserts, in debug builds, that the calling thread holds this lock.
+    ///
+    /// Put it at the top of helpers that assume their caller holds the lock, to
+    /// document and check that contract. With `deadlock-detection` the lock
+    /// knows its holder, so this panics unless the *current thread* holds it;
+    /// without the feature it can only check that *someone* holds it. In
+    /// release builds (no `debug_assertions`) it compiles to nothing.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// fn bump(lock: &SpinLock<u32>, value: &mut u32) {
+    ///     lock.debug_assert_held();
+    ///     *value += 1;
+    /// }
+    ///
+    /// let lock = SpinLock::new(0);
+    /// let mut guard = lock.lock();
+    /// bump(&lock, &mut guard);
+    /// ```
+    #[inline(always)]
+    #[track_caller]
+    pub fn debug_assert_held(&self) {
+        #[cfg(all(debug_assertions, feature = "deadlock-detection"))]
+        assert!(
+            *self.holder() == Some(std::thread::current().id()),
+            "SpinLock at {:p} is not held by the current thread",
+            self,
+        );
+        #[cfg(all(debug_assertions, not(feature = "deadlock-detection")))]
+        assert!(self.is_locked(), "SpinLock at {:p} is not held", self);
+    }
+
+    /// Whether the constructor set the `CONFIG_*` bit `flag`.
+    #[inline(always)]
+    fn has_config(&self, flag: u8) -> bool {
+        self.config & flag != 0
+    }
+
+    /// Clears the lock flag and, with the `parking` feature, wakes a parked waiter.
+    #[inline(always)]
+    fn release(&self) {
+        #[cfg(feature = "deadlock-detection")]
+        {
+            *self.holder() = None;
+        }
+        #[cfg(feature = "debug-guard-token")]
+        self.generation.fetch_add(1, core::sync::atomic::Ordering::Relaxed);
+        // Safety: only called on behalf of the current holder.
+        unsafe {
+            if self.has_config(CONFIG_SEQCST_RELEASE) {
+                self.raw.unlock_seqcst();
+            } else {
+                self.raw.unlock();
+            }
+        }
+        #[cfg(feature = "parking")]
+        self.parking.notify();
+    }
+
+    /// Acquires the lock, spinning until it becomes available.
+    ///
+    /// Uses an exponential [`BackOff`] to reduce contention.
+    /// Returns a [`SpinGuard`] which automatically releases the lock on drop.
+    ///
+    /// # Panics
+    /// With the `deadlock-detection` feature, panics if the calling thread already
+    /// holds this lock, instead of spinning forever.
+    ///
+    /// In builds with `debug_assertions`, panics with "possible deadlock" after
+    /// waiting `raw::DEBUG_DEADLOCK_TIMEOUT` without acquiring (without `std`,
+    /// after `raw::DEBUG_DEADLOCK_PAUSES` pause iterations).
+    #[inline]
+    #[cfg_attr(feature = "deadlock-detection", track_caller)]
+    pub fn lock(&self) -> SpinGuard<'_, T> {
+        #[cfg(feature = "deadlock-detection")]
+        self.assert_not_reentrant();
+        let spins = if self.has_config(CONFIG_LOW_LATENCY) {
+            self.raw
+                .lock_counted_with(|| BackOff::new_with_growth(1, GrowthKind::Additive(0)))
+        } else {
+            let rounds = self.raw.lock_counted_with(|| self.lock_backoff());
+            #[cfg(target_has_atomic = "32")]
+            if self.has_config(CONFIG_ADAPT_BACKOFF) {
+                self.adapt_backoff_start(rounds);
+            }
+            #[cfg(feature = "adaptive")]
+            self.record_spin_rounds(rounds);
+            #[cfg(feature = "contention-hint")]
+            self.record_contention(rounds);
+            rounds
+        };
+        #[cfg(feature = "profiling")]
+        if let Some(site) = self.site {
+            site.record(spins);
+        }
+        SpinGuard::new(self, spins)
+    }
+
+    /// Acquires the lock with `Relaxed` swaps and one `Acquire` fence on
+    /// success (only available with the `fenced-acquire` feature).
+    ///
+    /// Same semantics as [`lock`](Self::lock), with the default backoff; see
+    /// [fenced acquisition](crate::raw#fenced-acquisition) for the ordering
+    /// argument and when it may be faster.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let lock = SpinLock::new(1);
+    /// *lock.lock_fenced() += 1;
+    /// assert_eq!(*lock.lock(), 2);
+    /// ```
+    #[cfg(feature = "fenced-acquire")]
+    #[inline]
+    #[cfg_attr(feature = "deadlock-detection", track_caller)]
+    pub fn lock_fenced(&self) -> SpinGuard<'_, T> {
+        #[cfg(feature = "deadlock-detection")]
+        self.assert_not_reentrant();
+        let spins = self.raw.lock_fenced_counted();
+        SpinGuard::new(self, spins)
+    }
+
+    /// Acquires the lock like [`lock`](Self::lock), starting the backoff at a
+    /// spin count offset by `salt`.
+    ///
+    /// Threads that start contending together with identical backoffs escalate
+    /// on the same schedule and keep retrying at the same instants. Giving each
+    /// one a different salt (a core or thread number) starts them between
+    /// [`BackOff::START_VALUE`] and twice that, and since every round doubles,
+    /// their retries stay out of step. Works in `no_std`; with `std`,
+    /// [`lock_desync`](Self::lock_desync) picks the salt for you.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let lock = SpinLock::new(0);
+    /// let core_id = 3;
+    /// *lock.lock_salted(core_id) += 1;
+    /// assert_eq!(*lock.lock(), 1);
+    /// ```
+    #[inline]
+    #[cfg_attr(feature = "deadlock-detection", track_caller)]
+    pub fn lock_salted(&self, salt: u32) -> SpinGuard<'_, T> {
+        #[cfg(feature = "deadlock-detection")]
+        self.assert_not_reentrant();
+        let spins = self.raw.lock_counted_with(|| {
+            BackOff::new_with(BackOff::START_VALUE + salt % BackOff::START_VALUE)
+        });
+        SpinGuard::new(self, spins)
+    }
+
+    /// Like [`lock_salted`](Self::lock_salted), salted with a per-thread value
+    /// (only available with `std`).
+    ///
+    /// The salt is a hash of the calling thread's
+    /// [`ThreadId`](std::thread::ThreadId), computed once per thread, so each
+    /// thread escalates on its own, deterministic schedule without any PRNG.
+    /// Whether that beats [`lock`](Self::lock) depends on the machine: on the
+    /// `desync` group of `benches/contention.rs` (100 threads) the two have
+    /// measured within noise of each other, so benchmark your own workload.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    /// use std::thread;
+    ///
+    /// let lock = SpinLock::new(0);
+    /// thread::scope(|s| {
+    ///     for _ in 0..4 {
+    ///         s.spawn(|| *lock.lock_desync() += 1);
+    ///     }
+    /// });
+    /// assert_eq!(lock.into_inner(), 4);
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    #[cfg_attr(feature = "deadlock-detection", track_caller)]
+    pub fn lock_desync(&self) -> SpinGuard<'_, T> {
+        self.lock_salted(thread_salt())
+    }
+
+    /// Acquires the lock, like [`lock`](Self::lock), and returns it as a
+    /// [`SpinSession`] to be ended with [`SpinSession::leave`].
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::spinlock::SpinSession;
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// struct Transfer<'a> {
+    ///     session: Option<SpinSession<'a, Vec<u8>>>,
+    /// }
+    ///
+    /// let buffer = SpinLock::new(Vec::new());
+    /// let mut transfer = Transfer { session: Some(buffer.enter()) };
+    ///
+    /// // ... later, e.g. from a completion callback:
+    /// let mut session = transfer.session.take().unwrap();
+    /// session.data().push(7);
+    /// session.leave();
+    /// assert_eq!(*buffer.lock(), [7]);
+    /// ```
+    #[inline]
+    #[cfg_attr(feature = "deadlock-detection", track_caller)]
+    pub fn enter(&self) -> SpinSession<'_, T> {
+        SpinSession { guard: self.lock() }
+    }
+
+    /// Acquires the lock and returns a guard that only allows reading.
+    ///
+    /// Still mutually exclusive, like [`lock`](Self::lock); the returned
+    /// [`SpinReadGuard`] just has no `DerefMut`, so read-only critical sections
+    /// cannot mutate by accident.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let config = SpinLock::new(vec![1, 2]);
+    /// let view = config.lock_shared_ref();
+    /// assert_eq!(view.len(), 2);
+    /// // view.push(3); // error: cannot borrow data in dereference as mutable
+    /// ```
+    #[inline]
+    #[cfg_attr(feature = "deadlock-detection", track_caller)]
+    pub fn lock_shared_ref(&self) -> SpinReadGuard<'_, T> {
+        SpinReadGuard { inner: self.lock() }
+    }
+
+    /// Acquires the lock and returns the guard pinned, for `!Unpin` data.
+    ///
+    /// `Pin<SpinGuard>` gives out `Pin<&mut T>` through [`Pin::as_mut`], e.g. to
+    /// poll a future stored in the lock from a custom executor.
+    ///
+    /// # Safety
+    /// Pinning the lock does **not** by itself pin the data: the safe API still
+    /// lets any `&SpinLock<T>` (including `Pin::get_ref`) call [`lock`](Self::lock)
+    /// and move the value out through `&mut T` (`mem::swap`, `mem::replace`, ...).
+    /// Once this method has been called, the caller must guarantee that the data
+    /// is never moved again until it is dropped in place: never move it through a
+    /// plain guard, [`try_get_mut`](Self::try_get_mut) or
+    /// [`SpinGuard::leak`], and never take it out with
+    /// [`into_inner`](Self::into_inner) (which `Pin<&Self>` already rules out
+    /// while the lock is pinned). Given that, `Pin<&mut T>` is sound: the value
+    /// lives in the lock's `UnsafeCell`, which the pinned lock itself keeps at a
+    /// fixed address, and the guard only ever hands out references into it.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    /// use core::future::Future;
+    /// use core::task::{Context, Poll, Waker};
+    /// use std::pin::pin;
+    ///
+    /// let lock = pin!(SpinLock::new(async { 7 }));
+    /// let mut cx = Context::from_waker(Waker::noop());
+    /// // Safety: the future is only ever accessed through `lock_pinned`.
+    /// let mut guard = unsafe { lock.as_ref().lock_pinned() };
+    /// assert_eq!(guard.as_mut().poll(&mut cx), Poll::Ready(7));
+    /// ```
+    #[inline]
+    #[cfg_attr(feature = "deadlock-detection", track_caller)]
+    pub unsafe fn lock_pinned(self: Pin<&Self>) -> Pin<SpinGuard<'_, T>> {
+        // Safety: the caller upholds the pinning contract documented above.
+        unsafe { Pin::new_unchecked(self.get_ref().lock()) }
+    }
+
+    /// Unsafely releases the lock manually.
+    ///
+    /// # Safety
+    /// - Only call this if you *own* the lock.
+    /// - Misuse can cause data races or UB.
+    #[inline]
+    pub unsafe fn unlock(&self) {
+        self.release();
+    }
+
+    /// Forcibly releases the lock, returning whether it was held (only available
+    /// with the `recovery` feature).
+    ///
+    /// For recovery tooling that **knows** the holder is gone for good, e.g. a
+    /// thread that died (or was torn down) while it held the lock and never
+    /// dropped its guard. Unlike [`unlock`](Self::unlock), the caller does not
+    /// hold the lock itself, and finding it already free is not an error.
+    ///
+    /// # Safety
+    /// The caller must know that whoever holds the lock will never touch the
+    /// data or release the lock again, typically because the holding thread is
+    /// dead. If the holder is still alive, it keeps using the data while the
+    /// next `lock` hands it to someone else: a data race, which is undefined
+    /// behavior.
+    ///
+    /// The data is left as the holder left it, possibly mid-update; check its
+    /// invariants before trusting it. Releases the same way a guard would (with
+    /// `SeqCst` for [`new_seqcst`](Self::new_seqcst) locks, waking
+    /// [`lock_bounded`](Self::lock_bounded) sleepers, clearing the
+    /// `deadlock-detection` holder), and with `debug-guard-token` the dead
+    /// holder's guard, should it ever be dropped, is treated as stale: it does
+    /// not release the lock again.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let lock = SpinLock::new(0);
+    /// core::mem::forget(lock.lock()); // a holder that will never release
+    /// // Safety: the forgotten guard can never be used again.
+    /// assert!(unsafe { lock.force_unlock() });
+    /// assert!(!unsafe { lock.force_unlock() }, "already free");
+    /// *lock.lock() += 1;
+    /// ```
+    #[cfg(feature = "recovery")]
+    #[inline]
+    pub unsafe fn force_unlock(&self) -> bool {
+        use core::sync::atomic::Ordering::{Release, SeqCst};
+
+        #[cfg(feature = "deadlock-detection")]
+        {
+            *self.holder() = None;
+        }
+        #[cfg(feature = "debug-guard-token")]
+        self.generation.fetch_add(1, core::sync::atomic::Ordering::Relaxed);
+        let order = if self.has_config(CONFIG_SEQCST_RELEASE) { SeqCst } else { Release };
+        let was_locked = self.raw.force_unlock(order);
+        #[cfg(feature = "parking")]
+        if was_locked {
+            self.parking.notify();
+        }
+        was_locked
+    }
+
+    /// Acquires the lock, spinning for a bounded number of attempts before
+    /// parking the thread on the OS scheduler.
+    ///
+    /// The spinlock stays the primary mechanism: this first behaves like
+    /// [`try_lock_for`](Self::try_lock_for), and only if that fails does it block
+    /// on an internal `std::sync::Mutex`/`Condvar` pair until a release wakes it.
+    /// Parking is strictly a starvation backstop: a thread that keeps losing the
+    /// `swap` race to spinners stops burning CPU and is guaranteed to be
+    /// rescheduled by the OS, instead of spinning forever.
+    ///
+    /// # Cost
+    /// Only available with the `parking` feature (which implies `std`). Enabling it
+    /// adds the parking state to **every** `SpinLock` (a `Mutex<()>`, a `Condvar`
+    /// and a waiter count, typically 12–20 bytes). Releases check the waiter
+    /// count and only take the parking mutex to wake a thread when one is
+    /// actually parked, so uncontended releases stay cheap.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let lock = SpinLock::new(0);
+    /// *lock.lock_bounded() += 1;
+    /// assert_eq!(*lock.lock(), 1);
+    /// ```
+    #[cfg(feature = "parking")]
+    #[inline]
+    pub fn lock_bounded(&self) -> SpinGuard<'_, T> {
+        #[cfg(feature = "deadlock-detection")]
+        self.assert_not_reentrant();
+        match self.try_lock_for_with(BOUNDED_SPIN_ATTEMPTS, &BackOff::new()) {
+            Some(guard) => guard,
+            None => self.lock_parked(),
+        }
+    }
+
+    #[cfg(feature = "parking")]
+    #[cold]
+    fn lock_parked(&self) -> SpinGuard<'_, T> {
+        use std::sync::PoisonError;
+
+        use core::sync::atomic::{fence, Ordering::{Relaxed, SeqCst}};
+
+        let mut parked = self.parking.mutex.lock().unwrap_or_else(PoisonError::into_inner);
+        self.parking.waiters.fetch_add(1, Relaxed);
+        // Pairs with the fence in `Parking::notify`.
+        fence(SeqCst);
+        loop {
+            // Checked under the parking mutex, see `Parking::notify`.
+            if self.raw.try_lock() {
+                self.parking.waiters.fetch_sub(1, Relaxed);
+                return SpinGuard::new(self, BOUNDED_SPIN_ATTEMPTS as u32);
+            }
+            parked = self
+                .parking
+                .condvar
+                .wait(parked)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    /// Attempts to acquire the lock without blocking.
+    ///
+    /// Returns `Some(SpinGuard)` if the lock was free, or `None` otherwise.
+    ///
+    /// # No spurious failures
+    /// This is a guaranteed part of the API: `try_lock` returns `None` only if
+    /// another hold was genuinely in place at the moment of its single atomic
+    /// `swap`; it never fails the way a `compare_exchange_weak` may. Of several
+    /// threads racing for a free lock, exactly one succeeds. The parking
+    /// fallback of `lock_bounded` relies on this too. Should the implementation
+    /// ever move to a weak CAS, the strong behavior will be kept as a separate
+    /// `try_lock_strict`.
+    #[inline]
+    pub fn try_lock(&self) -> Option<SpinGuard<'_, T>> {
+        if self.raw.try_lock() {
+            Some(SpinGuard::new(self, 0))
+        } else {
+            None
+        }
+    }
+
+    /// A single [`try_lock`](Self::try_lock), phrased as a poll: `Ready(guard)` if
+    /// the lock was free, `Pending` otherwise.
+    ///
+    /// A building block for hand-written futures and pollers. It registers no
+    /// waker — a release does not wake anything — so the caller must arrange to
+    /// be polled again (e.g. `cx.waker().wake_by_ref()` before returning
+    /// `Pending`, or its own backoff loop). Works in `no_std`.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::{BackOff, SpinLock};
+    /// use core::task::Poll;
+    ///
+    /// let lock = SpinLock::new(1);
+    /// let backoff = BackOff::new();
+    ///
+    /// let mut guard = loop {
+    ///     match lock.poll_lock() {
+    ///         Poll::Ready(guard) => break guard,
+    ///         Poll::Pending => backoff.wait(),
+    ///     }
+    /// };
+    /// *guard += 1;
+    /// assert!(lock.poll_lock().is_pending());
+    /// drop(guard);
+    /// assert!(lock.poll_lock().is_ready());
+    /// ```
+    #[inline]
+    pub fn poll_lock(&self) -> core::task::Poll<SpinGuard<'_, T>> {
+        match self.try_lock() {
+            Some(guard) => core::task::Poll::Ready(guard),
+            None => core::task::Poll::Pending,
+        }
+    }
+
+    /// Attempts to acquire the lock and hands out the data as a bare `&mut T`.
+    ///
+    /// This is an escape hatch for FFI bridges that need to keep the lock held
+    /// across a boundary a [`SpinGuard`] cannot cross. On success the guard is
+    /// leaked: the lock **stays held** after the returned reference is gone, and
+    /// the caller must release it with [`unlock`](Self::unlock). Returns `None`,
+    /// without waiting, if the lock is already held.
+    ///
+    /// # Contract
+    /// - The returned borrow is the caller's proof of ownership. Call
+    ///   [`unlock`](Self::unlock) exactly once per `Some`, and only after the last
+    ///   use of the reference (and of any raw pointer derived from it).
+    /// - Using the reference after `unlock` is a data race; the borrow checker
+    ///   cannot catch this, because the reference borrows `self`, not the lock
+    ///   state.
+    /// - Forgetting to `unlock` is not unsafe, but deadlocks every later `lock`.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let lock = SpinLock::new(1u32);
+    /// let data = lock.try_get_mut().unwrap();
+    /// *data += 1;
+    /// assert!(lock.is_locked());
+    /// // Safety: acquired by `try_get_mut` above, `data` is not used afterwards.
+    /// unsafe { lock.unlock() };
+    /// assert_eq!(*lock.lock(), 2);
+    /// ```
+    #[inline]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_get_mut(&self) -> Option<&mut T> {
+        let guard = self.try_lock()?;
+        core::mem::forget(guard);
+        // SAFETY: the successful `try_lock` makes us the sole holder, and leaking
+        // the guard keeps the lock held past this call. No other `&T`/`&mut T` to
+        // the data can be created until the caller releases the lock through the
+        // unsafe `unlock`, whose contract requires this borrow to be dead by then.
+        Some(unsafe { &mut *self.data.get() })
+    }
+
+    /// Returns a shared reference to the data without touching the lock.
+    ///
+    /// For genuinely single-threaded phases (e.g. early boot, before any other
+    /// thread or interrupt handler can run), where taking the lock is pure
+    /// overhead.
+    ///
+    /// # Safety
+    /// For the whole lifetime of the returned reference, nothing may write the
+    /// data: no other thread, interrupt handler or guard may hold the lock for
+    /// writing, and no `&mut T` to it may exist (including one from
+    /// [`get_unchecked_mut`](Self::get_unchecked_mut)).
+    #[inline(always)]
+    pub unsafe fn get_unchecked(&self) -> &T {
+        // SAFETY: the caller guarantees no concurrent or aliasing mutable access,
+        // which is everything the lock would otherwise have enforced.
+        unsafe { &*self.data.get() }
+    }
+
+    /// Returns a mutable reference to the data without touching the lock.
+    ///
+    /// See [`get_unchecked`](Self::get_unchecked).
+    ///
+    /// # Safety
+    /// For the whole lifetime of the returned reference, it must be the only
+    /// access to the data: no thread, interrupt handler or guard may access it,
+    /// and no other reference from `get_unchecked`/`get_unchecked_mut` may exist.
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn get_unchecked_mut(&self) -> &mut T {
+        // SAFETY: the caller guarantees exclusive access, which is what holding
+        // the lock would otherwise have proven.
+        unsafe { &mut *self.data.get() }
+    }
+
+    /// Returns a raw pointer to the protected data, without locking.
+    ///
+    /// Creating the pointer is safe; dereferencing it is only sound while the
+    /// caller holds the lock (or otherwise guarantees exclusive access).
+    #[inline(always)]
+    pub const fn data_ptr(&self) -> *mut T {
+        self.data.get()
+    }
+
+    /// Attempts to acquire the lock without blocking, reporting why it failed.
+    ///
+    /// Same as [`try_lock`](Self::try_lock), but returns a [`TryLockError`] instead
+    /// of `None`, mirroring `std::sync::Mutex::try_lock`.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::{spinlock::TryLockError, SpinLock};
+    ///
+    /// let lock = SpinLock::new(0);
+    /// let guard = lock.try_lock_err().unwrap();
+    /// assert_eq!(lock.try_lock_err().err(), Some(TryLockError::WouldBlock));
+    /// drop(guard);
+    /// ```
+    #[inline]
+    pub fn try_lock_err(&self) -> Result<SpinGuard<'_, T>, TryLockError> {
+        self.try_lock().ok_or(TryLockError::WouldBlock)
+    }
+
+    /// Attempts to acquire the lock without blocking, reporting the holder's
+    /// thread on failure (only available with `std`).
+    ///
+    /// With the `deadlock-detection` feature the lock tracks its holder, and a
+    /// failed attempt returns `Err(Some(id))` naming the thread that holds it —
+    /// handy in contention warnings. Without that feature there is nothing to
+    /// report and failures are always `Err(None)`. Even with it, `Err(None)` is
+    /// possible when the attempt races with an acquisition or release, since the
+    /// holder is recorded just after the flag is set and cleared just before it
+    /// is released.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let lock = SpinLock::new(0);
+    /// let guard = lock.lock();
+    /// let holder = lock.try_lock_diagnostic().err().unwrap();
+    /// if cfg!(feature = "deadlock-detection") {
+    ///     assert_eq!(holder, Some(std::thread::current().id()));
+    /// } else {
+    ///     assert_eq!(holder, None);
+    /// }
+    /// drop(guard);
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn try_lock_diagnostic(&self) -> Result<SpinGuard<'_, T>, Option<std::thread::ThreadId>> {
+        match self.try_lock() {
+            Some(guard) => Ok(guard),
+            #[cfg(feature = "deadlock-detection")]
+            None => Err(*self.holder()),
+            #[cfg(not(feature = "deadlock-detection"))]
+            None => Err(None),
+        }
+    }
+
+    /// Checks whether the lock is currently held.
+    #[inline(always)]
+    pub fn is_locked(&self) -> bool {
+        self.raw.is_locked()
+    }
+
+    /// Checks whether the lock is currently held, without synchronizing.
+    ///
+    /// Uses a `Relaxed` load, which can be cheaper than the `Acquire` load of
+    /// [`is_locked`](Self::is_locked) in tight monitoring loops. The result is a
+    /// best-effort hint for statistics or assertions only: it establishes **no**
+    /// happens-before relationship with the holder, so it must never be used to
+    /// decide whether the protected data may be accessed.
+    #[inline(always)]
+    pub fn is_locked_relaxed(&self) -> bool {
+        self.raw.is_locked_relaxed()
+    }
+
+    /// Returns a copy of the data if the lock is free, without ever waiting.
+    ///
+    /// This is a cheap, best-effort read for `Copy` data such as shared counters:
+    /// it makes a single acquisition attempt, copies the value and releases
+    /// immediately. If another thread holds the lock, it returns `None` instead of
+    /// spinning.
+    ///
+    /// # Soundness boundary
+    /// A truly lock-free read (loading the value while a holder may be writing it)
+    /// is deliberately **not** offered, not even for word-sized `T`: the holder
+    /// writes through a plain `&mut T`, and a non-atomic write racing with any
+    /// concurrent read is a data race, i.e. undefined behavior, regardless of the
+    /// type's size. `peek` stays sound for every `T: Copy` by only copying while it
+    /// briefly owns the lock.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let hits = SpinLock::new(41u64);
+    /// *hits.lock() += 1;
+    /// assert_eq!(hits.peek(), Some(42));
+    ///
+    /// let _held = hits.lock();
+    /// assert_eq!(hits.peek(), None);
+    /// ```
+    #[inline]
+    pub fn peek(&self) -> Option<T>
+    where
+        T: Copy,
+    {
+        self.try_lock().map(|guard| *guard)
+    }
+
+    /// Makes exactly `attempts` acquisition attempts back to back, with no
+    /// backoff in between.
+    ///
+    /// Every attempt is a single `swap`; nothing spins, escalates or yields
+    /// between them. Use it when the holder is known to release within a handful
+    /// of instructions, where even the first [`BackOff`] round of
+    /// [`try_lock_for`](Self::try_lock_for) would overshoot. For anything longer,
+    /// prefer `try_lock_for`: tight retries hammer the cache line the holder
+    /// needs to release.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let lock = SpinLock::new(0);
+    /// let guard = lock.try_attempts(3).unwrap();
+    /// assert!(lock.try_attempts(3).is_none());
+    /// drop(guard);
+    /// ```
+    #[inline]
+    pub fn try_attempts(&self, attempts: usize) -> Option<SpinGuard<'_, T>> {
+        for attempt in 0..attempts {
+            if self.raw.try_lock() {
+                return Some(SpinGuard::new(self, attempt.min(u32::MAX as usize) as u32));
+            }
+        }
+        None
+    }
+
+    /// Tries to acquire the lock within a fixed number of spin attempts.
+    ///
+    /// Returns `Some(SpinGuard)` if successful, otherwise `None` after the given number of spins.
+    ///
+    /// `spins` counts attempts, with a backoff round between two of them. Rounds
+    /// grow from [`BackOff::START_VALUE`] but are capped at
+    /// [`TRY_LOCK_FOR_MAX_ROUND_SPINS`] iterations, so the whole call busy-waits
+    /// for at most `spins * TRY_LOCK_FOR_MAX_ROUND_SPINS` spin-loop iterations
+    /// and never yields; `try_lock_for(10)` means ten quick retries, not an
+    /// exponentially long wait. For back-to-back attempts with no waiting see
+    /// [`try_attempts`](Self::try_attempts); for an uncapped curve, pass your own
+    /// backoff to [`try_lock_for_with`](Self::try_lock_for_with).
+    ///
+    /// Each attempt first checks the flag with a plain load (test-and-test-and-set)
+    /// and adapts the [`BackOff`] to what it sees:
+    /// - lock solidly held: a [`BackOff::wait`] round, escalating as usual, that
+    ///   keeps re-reading the flag and ends early once it reads free, so a lock
+    ///   released mid-round is grabbed right away whatever the escalation step;
+    /// - lock observed free but the `swap` lost the race (a near miss): the lock
+    ///   is flickering between short holds, so the backoff
+    ///   [`relax`](BackOff::relax)es instead of escalating, keeping this waiter
+    ///   responsive enough to catch the next release.
+    #[inline]
+    pub fn try_lock_for(&self, spins: usize) -> Option<SpinGuard<'_, T>> {
+        self.try_lock_for_counted(spins).ok().map(|(guard, _)| guard)
+    }
+
+    /// Like [`try_lock_for`](Self::try_lock_for) (same capped backoff), but also
+    /// reports how many of the `spins` attempts were used.
+    ///
+    /// Returns `Ok((guard, used))` where `used` is in `1..=spins` (`1` means the
+    /// first attempt succeeded), or `Err(spins)` once the budget is exhausted.
+    /// `try_lock_for(n)` succeeds exactly when `used <= n`, so feeding `used` back
+    /// into the budget lets a call site tune its `spins` parameter over time.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let lock = SpinLock::new(0);
+    /// let (guard, used) = lock.try_lock_for_counted(8).unwrap();
+    /// assert_eq!(used, 1);
+    /// assert_eq!(lock.try_lock_for_counted(8).err(), Some(8));
+    /// drop(guard);
+    /// ```
+    #[inline]
+    pub fn try_lock_for_counted(&self, spins: usize) -> Result<(SpinGuard<'_, T>, usize), usize> {
+        self.try_lock_for_counted_with(spins, &Self::try_lock_for_backoff())
+    }
+
+    /// The capped backoff of [`try_lock_for`](Self::try_lock_for).
+    #[inline(always)]
+    fn try_lock_for_backoff() -> BackOff {
+        BackOff::with_config(&BackOffConfig {
+            max: TRY_LOCK_FOR_MAX_ROUND_SPINS,
+            ..BackOffConfig::DEFAULT
+        })
+    }
+
+    /// Like [`try_lock_for`](Self::try_lock_for), but waits with a caller-owned
+    /// [`BackOff`] instead of a fresh, capped one.
+    ///
+    /// The caller's growth curve is used as is, without the
+    /// [`TRY_LOCK_FOR_MAX_ROUND_SPINS`] cap; build it with a
+    /// [`BackOffConfig`] `max` to bound the wait.
+    ///
+    /// The backoff's state carries over between calls: escalation reached in a
+    /// failed attempt is where the next call starts, and successful calls do not
+    /// reset it. The caller manages that lifecycle, e.g. [`reset`](BackOff::reset)
+    /// after a quick success or [`relax`](BackOff::relax) after a run of them,
+    /// which lets a retry loop remember recent contention.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::{BackOff, SpinLock};
+    ///
+    /// let lock = SpinLock::new(0);
+    /// let backoff = BackOff::new();
+    ///
+    /// let held = lock.lock();
+    /// assert!(lock.try_lock_for_with(2, &backoff).is_none());
+    /// drop(held);
+    /// // The escalation from the failed attempt is remembered...
+    /// assert_eq!(backoff.current(), BackOff::START_VALUE * 4);
+    /// assert!(lock.try_lock_for_with(2, &backoff).is_some());
+    /// // ...until the caller decides otherwise.
+    /// backoff.reset();
+    /// ```
+    #[inline]
+    pub fn try_lock_for_with(&self, spins: usize, backoff: &BackOff) -> Option<SpinGuard<'_, T>> {
+        self.try_lock_for_counted_with(spins, backoff).ok().map(|(guard, _)| guard)
+    }
+
+    /// Shared loop of [`try_lock_for_counted`](Self::try_lock_for_counted) and
+    /// [`try_lock_for_with`](Self::try_lock_for_with).
+    #[inline]
+    fn try_lock_for_counted_with(
+        &self,
+        spins: usize,
+        backoff: &BackOff,
+    ) -> Result<(SpinGuard<'_, T>, usize), usize> {
+        for round in 0..spins {
+            if self.raw.is_locked_relaxed() {
+                // Poll the flag through the round so a release is caught at
+                // once, not after a long wait at a high escalation step.
+                backoff.wait_while(|| self.raw.is_locked_relaxed());
+                continue;
+            }
+            if self.raw.try_lock() {
+                let guard = SpinGuard::new(self, round.min(u32::MAX as usize) as u32);
+                return Ok((guard, round + 1));
+            }
+            // Near miss. Never relax down to 0, which no growth curve escalates from.
+            if backoff.current() > 1 {
+                backoff.relax();
+            }
+        }
+        Err(spins)
+    }
+
+    /// Acquires the lock like [`lock`](Self::lock), but gives up as soon as `irq`
+    /// is observed set while waiting.
+    ///
+    /// Equivalent to [`lock_interruptible_with`](Self::lock_interruptible_with)
+    /// with a fresh [`BackOff`].
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    /// use core::sync::atomic::AtomicBool;
+    ///
+    /// let lock = SpinLock::new(0);
+    /// let irq = AtomicBool::new(true);
+    /// let held = lock.lock();
+    /// assert!(lock.lock_interruptible(&irq).is_none());
+    /// drop(held);
+    /// // A free lock is still taken: the flag only interrupts waiting.
+    /// assert!(lock.lock_interruptible(&irq).is_some());
+    /// ```
+    #[inline]
+    pub fn lock_interruptible(
+        &self,
+        irq: &core::sync::atomic::AtomicBool,
+    ) -> Option<SpinGuard<'_, T>> {
+        self.lock_interruptible_with(irq, &BackOff::new())
+    }
+
+    /// Acquires the lock using the caller's `backoff`, polling `irq` before every
+    /// backoff round and returning `None` once it is set.
+    ///
+    /// The lock is attempted first, so a free lock is taken even if `irq` is
+    /// already set. On an interrupt the backoff is [`relax`](BackOff::relax)ed
+    /// once before returning, so a caller that retries with the same `BackOff`
+    /// after handling the interrupt does not resume at the escalated spin count
+    /// the aborted wait had reached (against a lock that may well be free by then).
+    ///
+    /// # Ordering
+    /// `irq` is read with `Acquire`: whatever the interrupting thread wrote
+    /// before setting it with `Release` (e.g. the reason for the interrupt) is
+    /// visible when `None` is returned. The flag is never cleared here; resetting
+    /// it is up to the caller.
+    #[inline]
+    pub fn lock_interruptible_with(
+        &self,
+        irq: &core::sync::atomic::AtomicBool,
+        backoff: &BackOff,
+    ) -> Option<SpinGuard<'_, T>> {
+        let mut rounds: u32 = 0;
+        loop {
+            if self.raw.try_lock() {
+                return Some(SpinGuard::new(self, rounds));
+            }
+            if irq.load(core::sync::atomic::Ordering::Acquire) {
+                // Never relax down to 0, which no growth curve escalates from.
+                if backoff.current() > 1 {
+                    backoff.relax();
+                }
+                return None;
+            }
+            rounds = rounds.saturating_add(1);
+            backoff.wait();
+        }
+    }
+
+    /// Tries to acquire the lock until `clock` reaches `deadline_ticks`.
+    ///
+    /// The [`Clock`] returns the current time in arbitrary ticks (e.g. a hardware
+    /// cycle or timer counter, or [`InstantClock`](crate::backoff::InstantClock)
+    /// under `std`), so this works in `no_std` without any dependency on
+    /// `std::time`. The lock is always attempted at least once, even if the deadline
+    /// has already passed.
+    ///
+    /// Returns `Some(SpinGuard)` on success, or `None` once `clock.now() >= deadline_ticks`.
+    ///
+    /// # Granularity
+    /// To keep clock reads out of the hot loop, the clock is only sampled every
+    /// few backoff rounds. Since backoff rounds grow exponentially, the call may
+    /// overshoot the deadline by up to that many rounds of spinning; use a
+    /// deadline slightly earlier than your hard limit if that matters.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    /// use core::cell::Cell;
+    ///
+    /// let ticks = Cell::new(0u64);
+    /// let now = || {
+    ///     ticks.set(ticks.get() + 1);
+    ///     ticks.get()
+    /// };
+    ///
+    /// let lock = SpinLock::new(0);
+    /// assert!(lock.try_lock_for_ticks(100, &now).is_some());
+    /// ```
+    #[inline]
+    pub fn try_lock_for_ticks(
+        &self,
+        deadline_ticks: u64,
+        clock: &impl Clock,
+    ) -> Option<SpinGuard<'_, T>> {
+        let backoff = BackOff::new();
+        let mut rounds: u32 = 0;
+        loop {
+            if self.raw.try_lock() {
+                return Some(SpinGuard::new(self, rounds));
+            }
+            if rounds.is_multiple_of(TICK_SAMPLE_INTERVAL) && clock.now() >= deadline_ticks {
+                return None;
+            }
+            rounds = rounds.wrapping_add(1);
+            backoff.wait();
+        }
+    }
+
+    /// Spins until the lock is observed free, **without** acquiring it.
+    ///
+    /// Uses an exponential [`BackOff`] between checks, like [`lock`](Self::lock).
+    /// This is a coordination primitive, not an exclusion one: by the time it
+    /// returns, another thread may already have re-acquired the lock. It only
+    /// guarantees that the lock was free at *some* point after the call started,
+    /// and (through the `Acquire` load) that everything the previous holder wrote
+    /// before releasing is visible. Take the lock if you need the state to stay
+    /// as observed.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let lock = SpinLock::new(());
+    /// lock.wait_until_unlocked(); // returns at once: nobody holds it
+    /// assert!(!lock.is_locked());
+    /// ```
+    #[inline]
+    pub fn wait_until_unlocked(&self) {
+        let backoff = BackOff::new();
+        while self.raw.is_locked() {
+            backoff.wait();
+        }
+    }
+
+    /// Like [`wait_until_unlocked`](Self::wait_until_unlocked), but gives up after
+    /// `spins` backoff rounds.
+    ///
+    /// Returns `true` if the lock was observed free (with the same caveat: it may
+    /// be held again already), `false` if it stayed held for every check.
+    #[inline]
+    pub fn wait_until_unlocked_for(&self, spins: usize) -> bool {
+        let backoff = BackOff::new();
+        for _ in 0..spins {
+            if !self.raw.is_locked() {
+                return true;
+            }
+            backoff.wait();
+        }
+        false
+    }
+
+    /// Returns `true` if `guard` was obtained from this lock.
+    ///
+    /// A pointer comparison, cheap enough for `debug_assert!`s in APIs that take
+    /// both a lock and a guard.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let a = SpinLock::new(0);
+    /// let b = SpinLock::new(0);
+    /// let guard = a.lock();
+    /// assert!(a.owns(&guard));
+    /// assert!(!b.owns(&guard));
+    /// ```
+    #[inline(always)]
+    pub fn owns(&self, guard: &SpinGuard<'_, T>) -> bool {
+        core::ptr::eq(self, guard.guard)
+    }
+
+    /// Returns an identifier for this lock, derived from its address.
+    ///
+    /// Useful as a key for logging or for building a lock-acquisition-order graph.
+    /// The id is stable for as long as the lock is alive and not moved; since it
+    /// is taken through `&self`, it cannot change while you hold that borrow.
+    /// It is **only** meaningful while the lock is alive: once a lock is dropped,
+    /// its address (and thus its id) may be reused by a new lock.
+    ///
+    /// The [`fmt::Pointer`] impl (`{:p}`) prints the same address.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let a = SpinLock::new(0);
+    /// let b = SpinLock::new(0);
+    /// assert_ne!(a.id(), b.id());
+    /// assert_eq!(format!("{:p}", a), format!("{:#x}", a.id()));
+    /// ```
+    #[inline(always)]
+    pub fn id(&self) -> usize {
+        self as *const Self as usize
+    }
+
+    /// Acquires the lock once `pred` holds for the data, monitor-style.
+    ///
+    /// Each attempt locks, evaluates `pred` under the lock, and either returns
+    /// the guard (so the condition still holds for the caller) or releases and
+    /// backs off with an escalating [`BackOff`] before re-checking. Releasing
+    /// between checks is what lets another thread change the data.
+    ///
+    /// # Cost
+    /// This is a busy-wait with no wakeups: every re-check takes the lock and
+    /// runs `pred`, competing with the very thread that has to make it true, and
+    /// the thread burns CPU (yielding under `std` as the backoff escalates) for as
+    /// long as the condition stays false. Fine for conditions that become true
+    /// within microseconds; for longer waits use
+    /// [`SpinCondvar`](crate::SpinCondvar) or an OS primitive, and see
+    /// [`lock_when_for`](Self::lock_when_for) for a bounded variant.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    /// use std::thread;
+    ///
+    /// let queue = SpinLock::new(Vec::new());
+    /// thread::scope(|s| {
+    ///     s.spawn(|| queue.lock().push(42));
+    ///     let mut items = queue.lock_when(|q| !q.is_empty());
+    ///     assert_eq!(items.pop(), Some(42));
+    /// });
+    /// ```
+    #[inline]
+    #[cfg_attr(feature = "deadlock-detection", track_caller)]
+    pub fn lock_when(&self, pred: impl Fn(&T) -> bool) -> SpinGuard<'_, T> {
+        let backoff = BackOff::new();
+        loop {
+            let guard = self.lock();
+            if pred(&guard) {
+                return guard;
+            }
+            drop(guard);
+            backoff.wait();
+        }
+    }
+
+    /// Like [`lock_when`](Self::lock_when), but gives up after `spins` failed
+    /// checks.
+    ///
+    /// Returns the guard once `pred` holds (checking at least once even for
+    /// `spins == 0`), or `None` if it was still false after `spins` backoff rounds.
+    #[inline]
+    #[cfg_attr(feature = "deadlock-detection", track_caller)]
+    pub fn lock_when_for(&self, spins: usize, pred: impl Fn(&T) -> bool) -> Option<SpinGuard<'_, T>> {
+        let backoff = BackOff::new();
+        let mut round = 0;
+        loop {
+            let guard = self.lock();
+            if pred(&guard) {
+                return Some(guard);
+            }
+            drop(guard);
+            if round == spins {
+                return None;
+            }
+            round += 1;
+            backoff.wait();
+        }
+    }
+
+    /// Acquires two locks without risking a lock-order deadlock, returning both
+    /// guards.
+    ///
+    /// The locks are always taken in address order (by [`id`](Self::id), the same
+    /// order [`PartialEq`] uses), whatever the argument order, so two threads
+    /// calling `lock_two(a, b)` and `lock_two(b, a)` cannot deadlock. A lock
+    /// nested as the *data* of another shares its address; such pairs are ordered
+    /// by the address of their flags instead. Allocation-free and `no_std`.
+    ///
+    /// # Panics
+    /// If `a` and `b` are the same lock, which cannot be held twice.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let from = SpinLock::new(100);
+    /// let to = SpinLock::new(0);
+    ///
+    /// let (mut src, mut dst) = SpinLock::lock_two(&from, &to);
+    /// *src -= 30;
+    /// *dst += 30;
+    /// drop((src, dst));
+    /// assert_eq!((*from.lock(), *to.lock()), (70, 30));
+    /// ```
+    #[inline]
+    #[cfg_attr(feature = "deadlock-detection", track_caller)]
+    pub fn lock_two<'a, U>(
+        a: &'a SpinLock<T>,
+        b: &'a SpinLock<U>,
+    ) -> (SpinGuard<'a, T>, SpinGuard<'a, U>) {
+        let flag_a = &a.raw as *const RawSpinLock as usize;
+        let flag_b = &b.raw as *const RawSpinLock as usize;
+        assert!(flag_a != flag_b, "SpinLock::lock_two called with the same lock twice");
+        if (a.id(), flag_a) < (b.id(), flag_b) {
+            let first = a.lock();
+            (first, b.lock())
+        } else {
+            let second = b.lock();
+            (a.lock(), second)
+        }
+    }
+
+    /// Runs a closure with exclusive access to the data.
+    ///
+    /// This is a convenience wrapper around [`lock()`] that automatically releases
+    /// the lock when the closure returns.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    /// let lock = SpinLock::new(0i32);
+    /// lock.with_lock(|data| {
+    ///     *data += 1;
+    /// });
+    /// ```
+    #[inline]
+    pub fn with_lock<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        let mut guard = self.lock();
+        f(&mut *guard)
+    }
+
+    /// Runs a fallible closure with exclusive access to the data, like
+    /// [`with_lock`](Self::with_lock), and returns its `Result`.
+    ///
+    /// The lock is released before this method returns, on `Ok` and `Err`
+    /// alike, so the error can travel up with `?` without the caller ever
+    /// holding the lock. Changes `f` made before failing are kept; roll them
+    /// back inside `f` if the error path must leave the data untouched.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// fn withdraw(balance: &SpinLock<u32>, amount: u32) -> Result<u32, &'static str> {
+    ///     let left = balance.try_with_lock_result(|b| {
+    ///         *b = b.checked_sub(amount).ok_or("insufficient funds")?;
+    ///         Ok(*b)
+    ///     })?;
+    ///     Ok(left)
+    /// }
+    ///
+    /// let balance = SpinLock::new(10);
+    /// assert_eq!(withdraw(&balance, 4), Ok(6));
+    /// assert_eq!(withdraw(&balance, 7), Err("insufficient funds"));
+    /// assert!(!balance.is_locked());
+    /// ```
+    #[inline]
+    pub fn try_with_lock_result<R, E>(
+        &self,
+        f: impl FnOnce(&mut T) -> Result<R, E>,
+    ) -> Result<R, E> {
+        let mut guard = self.lock();
+        let result = f(&mut *guard);
+        drop(guard);
+        result
+    }
+
+    /// Locks, computes a value from a shared reference to the data, and unlocks.
+    ///
+    /// The read-only counterpart of [`with_lock`](Self::with_lock), for quick
+    /// projections that return owned data (`lock.view(|v| v.len())`) without
+    /// exposing a guard. Named `view` rather than `map` to keep that name free for
+    /// guard mapping.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let names = SpinLock::new(vec!["a", "b"]);
+    /// assert_eq!(names.view(|v| v.len()), 2);
+    /// assert!(!names.is_locked());
+    /// ```
+    #[inline]
+    pub fn view<U>(&self, f: impl FnOnce(&T) -> U) -> U {
+        let guard = self.lock();
+        f(&*guard)
+    }
+
+    /// Runs `on_locked` with the data if the lock is free, or `on_contended`
+    /// (without the lock) if it is held.
+    ///
+    /// A single [`try_lock`](Self::try_lock) attempt decides the branch; the lock
+    /// is released as soon as `on_locked` returns. Handy for opportunistic work
+    /// that may be skipped or deferred under contention.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    /// use std::sync::atomic::{AtomicU32, Ordering::Relaxed};
+    ///
+    /// let stats = SpinLock::new(0u32);
+    /// let skipped = AtomicU32::new(0);
+    ///
+    /// let recorded = stats.try_lock_or_else(|n| { *n += 1; true }, || {
+    ///     skipped.fetch_add(1, Relaxed);
+    ///     false
+    /// });
+    /// assert!(recorded);
+    /// assert_eq!(*stats.lock(), 1);
+    /// ```
+    #[inline]
+    pub fn try_lock_or_else<R>(
+        &self,
+        on_locked: impl FnOnce(&mut T) -> R,
+        on_contended: impl FnOnce() -> R,
+    ) -> R {
+        match self.try_lock() {
+            Some(mut guard) => on_locked(&mut *guard),
+            None => on_contended(),
+        }
+    }
+
+    /// Overwrites the data with `new` if it equals `expected`, all under one
+    /// acquisition; returns whether the swap happened.
+    ///
+    /// Compare-and-set for data that has no atomic type of its own, e.g. an
+    /// enum-based state machine. On a mismatch the data is left untouched and
+    /// `new` is dropped.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// #[derive(PartialEq, Debug)]
+    /// enum State { Idle, Running }
+    ///
+    /// let state = SpinLock::new(State::Idle);
+    /// assert!(state.compare_and_set(&State::Idle, State::Running));
+    /// assert!(!state.compare_and_set(&State::Idle, State::Running));
+    /// assert_eq!(*state.lock(), State::Running);
+    /// ```
+    #[inline]
+    pub fn compare_and_set(&self, expected: &T, new: T) -> bool
+    where
+        T: PartialEq,
+    {
+        let mut guard = self.lock();
+        if *guard == *expected {
+            *guard = new;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Swaps in `new` if `pred` accepts the current value, all under one
+    /// acquisition.
+    ///
+    /// Returns `Ok(old)` with the replaced value, or `Err(new)` handing `new`
+    /// back untouched when `pred` returns `false`. A closure-based
+    /// [`compare_and_set`](Self::compare_and_set) for conditions that are not
+    /// plain equality; both values are returned rather than dropped, so their
+    /// destructors run after the lock is released.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// // Keep only the newest timestamp.
+    /// let latest = SpinLock::new(10u64);
+    /// assert_eq!(latest.replace_if(|&current| 12 > current, 12), Ok(10));
+    /// assert_eq!(latest.replace_if(|&current| 11 > current, 11), Err(11));
+    /// assert_eq!(*latest.lock(), 12);
+    /// ```
+    #[inline]
+    pub fn replace_if(&self, pred: impl FnOnce(&T) -> bool, new: T) -> Result<T, T> {
+        let mut guard = self.lock();
+        if pred(&guard) {
+            Ok(core::mem::replace(&mut *guard, new))
+        } else {
+            Err(new)
+        }
+    }
+
+    /// Overwrites the data with `value`, dropping the old value under the lock.
+    ///
+    /// Same as `*lock.lock() = value`. Use [`replace`](Self::replace) to get the
+    /// old value back instead.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let state = SpinLock::new("idle");
+    /// state.set("busy");
+    /// assert_eq!(*state.lock(), "busy");
+    /// ```
+    #[inline]
+    pub fn set(&self, value: T) {
+        *self.lock() = value;
+    }
+
+    /// Stores `value` and returns the previous value.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let state = SpinLock::new(1);
+    /// assert_eq!(state.replace(2), 1);
+    /// assert_eq!(*state.lock(), 2);
+    /// ```
+    #[inline]
+    pub fn replace(&self, value: T) -> T {
+        core::mem::replace(&mut *self.lock(), value)
+    }
+
+    /// Replaces the data with a freshly built value, dropping the old one under
+    /// the lock.
+    ///
+    /// Unlike a `replace` returning the old value, nothing is moved out: the old
+    /// value is dropped in place and `f`'s result is assigned into the lock, which
+    /// suits reset-to-fresh patterns on large `T`. `f` runs while the lock is held
+    /// (so its result can be written directly into place), so keep it cheap.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let buffer = SpinLock::new(vec![1, 2, 3]);
+    /// buffer.reinit(|| Vec::with_capacity(16));
+    /// assert!(buffer.lock().is_empty());
+    /// ```
+    #[inline]
+    pub fn reinit(&self, f: impl FnOnce() -> T) {
+        let mut guard = self.lock();
+        *guard = f();
+    }
+
+    /// Runs `use_it` on one part of the data, selected by `project`, under the lock.
+    ///
+    /// A closure-based alternative to a mapped guard: locks, projects to the
+    /// field, runs `use_it`, and unlocks when it returns.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// struct Stats { hits: u64, misses: u64 }
+    ///
+    /// let stats = SpinLock::new(Stats { hits: 0, misses: 0 });
+    /// let hits = stats.with_field(|s| &mut s.hits, |hits| { *hits += 1; *hits });
+    /// assert_eq!(hits, 1);
+    /// assert_eq!(stats.lock().misses, 0);
+    /// ```
+    #[inline]
+    pub fn with_field<U: ?Sized, R>(
+        &self,
+        project: impl FnOnce(&mut T) -> &mut U,
+        use_it: impl FnOnce(&mut U) -> R,
+    ) -> R {
+        self.with_lock(|data| use_it(project(data)))
+    }
+
+    /// Runs `body` with exclusive access to the data, calling `on_panic` if it panics.
+    ///
+    /// If `body` unwinds, `on_panic` is invoked with the data **while the lock is
+    /// still held**, giving you a chance to restore any invariants before the lock
+    /// is released and the panic continues to propagate. If `body` returns normally,
+    /// `on_panic` is never called.
+    ///
+    /// This is a lightweight invariant-repair hook, not full lock poisoning. When the
+    /// crate is built with `panic = "abort"` (common in `no_std`), `on_panic` never runs.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let lock = SpinLock::new(vec![1, 2, 3]);
+    /// let len = lock.scoped(|v| v.len(), |v| v.clear());
+    /// assert_eq!(len, 3);
+    /// ```
+    #[inline]
+    pub fn scoped<R>(&self, body: impl FnOnce(&mut T) -> R, on_panic: impl FnOnce(&mut T)) -> R {
+        // Runs the repair callback from `Drop`, i.e. only while unwinding out of `body`.
+        // The guard is a field, so it is released *after* the callback returns.
+        struct Repair<'a, T, F: FnOnce(&mut T)> {
+            guard: SpinGuard<'a, T>,
+            on_panic: Option<F>,
+        }
+
+        impl<T, F: FnOnce(&mut T)> Drop for Repair<'_, T, F> {
+            fn drop(&mut self) {
+                if let Some(on_panic) = self.on_panic.take() {
+                    on_panic(&mut self.guard);
+                }
+            }
+        }
+
+        let mut repair = Repair {
+            guard: self.lock(),
+            on_panic: Some(on_panic),
+        };
+        let result = body(&mut repair.guard);
+        // `body` returned normally: disarm the repair hook.
+        repair.on_panic = None;
+        result
+    }
+}
+
+/// Numeric conveniences that do a whole update under a single acquisition.
+///
+/// A hot loop that locks once per `+= 1` spends most of its time acquiring and
+/// releasing. Batch the work locally and apply it with one call instead:
+///
+/// ```
+/// use axiom_spinlock::SpinLock;
+///
+/// static HITS: SpinLock<u64> = SpinLock::new(0);
+///
+/// let mut local = 0;
+/// for _ in 0..1_000 {
+///     local += 1; // hot loop touches no shared state
+/// }
+/// HITS.add(local); // one lock for the whole batch
+/// assert_eq!(*HITS.lock(), 1_000);
+/// ```
+///
+/// For anything beyond arithmetic, the same pattern is [`SpinLock::with_lock`]
+/// around the bulk work.
+impl<T: Copy> SpinLock<T> {
+    /// Adds `n` to the value, locking once.
+    #[inline]
+    pub fn add(&self, n: T)
+    where
+        T: AddAssign,
+    {
+        *self.lock() += n;
+    }
+
+    /// Subtracts `n` from the value, locking once.
+    #[inline]
+    pub fn sub(&self, n: T)
+    where
+        T: SubAssign,
+    {
+        *self.lock() -= n;
+    }
+
+    /// Adds `n` to the value and returns the previous value, locking once.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let next_id = SpinLock::new(10u32);
+    /// assert_eq!(next_id.fetch_add(1), 10);
+    /// assert_eq!(next_id.fetch_add(1), 11);
+    /// ```
+    #[inline]
+    pub fn fetch_add(&self, n: T) -> T
+    where
+        T: AddAssign,
+    {
+        let mut guard = self.lock();
+        let previous = *guard;
+        *guard += n;
+        previous
+    }
+}
+
+/// Wiping helpers for secret data (requires the `zeroize` feature).
+#[cfg(feature = "zeroize")]
+impl<T: zeroize::Zeroize> SpinLock<T> {
+    /// Locks the lock and wipes the data in place.
+    ///
+    /// Uses [`Zeroize`](zeroize::Zeroize), whose volatile writes the compiler
+    /// cannot optimize away. The lock stays usable; the data is simply zeroed
+    /// (or emptied, for collections).
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let key = SpinLock::new([0xA5u8; 32]);
+    /// key.zeroize();
+    /// assert_eq!(*key.lock(), [0; 32]);
+    /// ```
+    #[inline]
+    pub fn zeroize(&self) {
+        self.lock().zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T: zeroize::Zeroize> SpinLock<zeroize::Zeroizing<T>> {
+    /// Creates a lock whose data is wiped when the lock is dropped (requires
+    /// the `zeroize` feature).
+    ///
+    /// Rust cannot give `SpinLock<T>` a destructor only for `T: Zeroize`, so
+    /// wipe-on-drop comes from wrapping the data in
+    /// [`Zeroizing`](zeroize::Zeroizing); guards deref to it, and it derefs to
+    /// `T`.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let key = SpinLock::new_zeroizing([7u8; 32]);
+    /// assert_eq!(key.lock()[0], 7);
+    /// drop(key); // the 32 bytes are zeroed here
+    /// ```
+    #[inline]
+    pub fn new_zeroizing(data: T) -> Self {
+        Self::new(zeroize::Zeroizing::new(data))
+    }
+}
+
+/// Wipes the data through `&mut`, without locking.
+#[cfg(feature = "zeroize")]
+impl<T: zeroize::Zeroize> zeroize::Zeroize for SpinLock<T> {
+    fn zeroize(&mut self) {
+        self.get_mut().zeroize();
+    }
+}
+
+/// Buffer-swap helpers for locks around a `Vec` (requires the `alloc` feature).
+#[cfg(feature = "alloc")]
+impl<T> SpinLock<alloc::vec::Vec<T>> {
+    /// Takes all elements out, leaving an empty `Vec` behind, and returns them.
+    ///
+    /// The lock is held only for the swap, so the returned items can be processed
+    /// without blocking producers. The new `Vec` starts without capacity; use
+    /// [`replace`](SpinLock::replace) with a pre-allocated buffer to keep one.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let events = SpinLock::new(vec!["connect", "read"]);
+    /// for event in events.drain_locked() {
+    ///     // the lock is already free here
+    ///     assert!(!events.is_locked());
+    ///     let _ = event;
+    /// }
+    /// assert!(events.lock().is_empty());
+    /// ```
+    #[inline]
+    pub fn drain_locked(&self) -> alloc::vec::Vec<T> {
+        core::mem::take(&mut *self.lock())
+    }
+}
+
+/// Slot helpers for locks around an `Option`, e.g. a reusable resource that is
+/// filled lazily and handed out again with [`take`](SpinLock::take).
+impl<T> SpinLock<Option<T>> {
+    /// Fills the slot with `f()` if it is empty; returns `true` if this call did.
+    ///
+    /// The check and the insert happen under one lock, so `f` runs only when the
+    /// slot is `None` and at most one of several racing callers fills it. `f`
+    /// runs while the lock is held, so keep it cheap.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let slot = SpinLock::new(None);
+    /// assert!(slot.get_or_insert_with(|| 1));
+    /// assert!(!slot.get_or_insert_with(|| unreachable!()));
+    /// assert_eq!(*slot.lock(), Some(1));
+    /// ```
+    #[inline]
+    pub fn get_or_insert_with(&self, f: impl FnOnce() -> T) -> bool {
+        let mut guard = self.lock();
+        if guard.is_some() {
+            return false;
+        }
+        *guard = Some(f());
+        true
+    }
+
+    /// Empties the slot and returns what it held.
+    ///
+    /// Unlike a once-cell, the slot can be filled again afterwards.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLock;
+    ///
+    /// let slot = SpinLock::new(Some("conn"));
+    /// assert_eq!(slot.take(), Some("conn"));
+    /// assert_eq!(slot.take(), None);
+    /// ```
+    #[inline]
+    pub fn take(&self) -> Option<T> {
+        self.lock().take()
+    }
+}
+
+impl<T> Deref for SpinGuard<'_, T> {
+    type Target = T;
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        unsafe { &*(self.guard.data.get()) }
+    }
+}
+
+impl<T> DerefMut for SpinGuard<'_, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *self.guard.data.get() }
+    }
+}
+
+// Safety: SpinLock enforces mutual exclusion via atomic operations.
+unsafe impl<T: Send> Send for SpinLock<T> {}
+unsafe impl<T: Send> Sync for SpinLock<T> {}
+
+/// Clones the protected value into a new, unlocked lock with the same
+/// per-lock settings (including the [profiling](crate::profiling) name), but
+/// fresh runtime state such as contention counters.
+///
+/// **Blocks** until `self` can be locked, and deadlocks if the caller already
+/// holds it. [`clone_from`](Clone::clone_from) locks only `source`: `self` is
+/// borrowed mutably, so its data is overwritten in place with
+/// [`T::clone_from`](Clone::clone_from), reusing its allocations (e.g. a `Vec`'s
+/// capacity).
+impl<T: Clone> Clone for SpinLock<T> {
+    fn clone(&self) -> Self {
+        let mut lock = SpinLock::new(self.lock().clone());
+        lock.copy_settings(self);
+        lock
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.get_mut().clone_from(&*source.lock());
+        self.copy_settings(source);
+    }
+}
+
+impl<T> SpinLock<T> {
+    /// Copies the per-lock settings chosen at construction from `source`.
+    #[inline]
+    fn copy_settings(&mut self, source: &Self) {
+        self.config = source.config;
+        #[cfg(target_has_atomic = "32")]
+        {
+            self.backoff_start = core::sync::atomic::AtomicU32::new(source.backoff_start());
+        }
+        #[cfg(not(target_has_atomic = "32"))]
+        {
+            self.backoff_start = source.backoff_start;
+        }
+        #[cfg(feature = "profiling")]
+        {
+            self.site = source.site;
+        }
+    }
+}
+
+/// Compares the protected values, locking both locks.
+///
+/// **Blocks** until both locks are acquired. They are always taken in address
+/// order (lower [`id`](SpinLock::id) first), so two threads comparing the same
+/// pair in opposite order cannot deadlock each other. Comparing a lock with
+/// itself locks it once.
+impl<T: PartialEq> PartialEq for SpinLock<T> {
+    fn eq(&self, other: &Self) -> bool {
+        if core::ptr::eq(self, other) {
+            let guard = self.lock();
+            return PartialEq::eq(&*guard, &*guard);
+        }
+        let (mine, theirs) = if self.id() < other.id() {
+            let mine = self.lock();
+            (mine, other.lock())
+        } else {
+            let theirs = other.lock();
+            (self.lock(), theirs)
+        };
+        *mine == *theirs
+    }
+}
+
+impl<T: Eq> Eq for SpinLock<T> {}
+
+/// Hashes the protected value, locking the lock for the duration.
+///
+/// **Blocks** until the lock is acquired, and deadlocks if the calling thread
+/// already holds it (e.g. hashing from inside a critical section, or a `T`
+/// whose `Hash` reaches back into the same lock). Consistent with
+/// [`PartialEq`]: equal values hash equally. As a map key, the value must not
+/// change while it is in the map, so keep this to frozen, read-mostly data.
+impl<T: core::hash::Hash> core::hash::Hash for SpinLock<T> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.lock().hash(state);
+    }
+}
+
+/// Formats the lock's address, i.e. its [`id`](SpinLock::id).
+impl<T> fmt::Pointer for SpinLock<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Pointer::fmt(&(self as *const Self), f)
+    }
+}
+
+
+#[cfg(test)]
+mod test{
+    #[test]
+    fn test_basic_lock_unlock() {
+        use crate::SpinLock;
+
+        let lock = SpinLock::new(10);
+
+        {
+            let mut guard = lock.lock();
+            *guard += 5;
+            assert_eq!(*guard, 15);
+        } // guard dropped here, automatically unlocks
+
+        assert!(!lock.is_locked(), "Lock should be released after guard drop");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_concurrent_access() {
+        use crate::SpinLock;
+        use std::sync::Arc;
+        use std::thread;
+
+        let lock = Arc::new(SpinLock::new(0usize));
+        let mut handles = vec![];
+
+        for _ in 0..8 {
+            let lock_cloned = lock.clone();
+            handles.push(thread::spawn(move || {
+                for _ in 0..10_000 {
+                    let mut guard = lock_cloned.lock();
+                    *guard += 1;
+                }
+            }));
+        }
+
+        for h in handles {
+            h.join().unwrap();
+        }
+
+        let final_value = *lock.lock();
+        assert_eq!(final_value, 8 * 10_000, "Counter should match total increments");
+    }
+
+    #[test]
+    fn test_try_lock_for_behavior() {
+        use crate::SpinLock;
+
+        let lock = SpinLock::new(42);
+
+        // Acquire lock once
+        let _guard = lock.lock();
+
+        // Attempt to acquire again (should fail)
+        assert!(lock.try_lock_for(10).is_none(), "Lock should not be acquirable while held");
+
+        // After drop, should succeed
+        drop(_guard);
+        let guard2 = lock.try_lock_for(1000);
+        assert!(guard2.is_some(), "Lock should succeed after previous guard drop");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_scoped_runs_on_panic_only_when_body_panics() {
+        use crate::SpinLock;
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let lock = SpinLock::new(0u32);
+        let mut repairs = 0;
+
+        // Normal return: the repair hook must not run.
+        let out = lock.scoped(
+            |v| {
+                *v += 1;
+                *v
+            },
+            |_| repairs += 1,
+        );
+        assert_eq!(out, 1);
+        assert_eq!(repairs, 0, "on_panic ran without a panic");
+
+        // Panicking body: the hook runs with the lock still held.
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            lock.scoped(
+                |v| {
+                    *v = 999;
+                    panic!("broken invariant");
+                },
+                |v| {
+                    assert!(lock.is_locked(), "on_panic should run under the lock");
+                    repairs += 1;
+                    *v = 1;
+                },
+            )
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(repairs, 1, "on_panic should run exactly once");
+        assert!(!lock.is_locked(), "Lock should be released after unwinding");
+        assert_eq!(*lock.lock(), 1, "on_panic should have repaired the data");
+    }
+
+    #[test]
+    fn test_try_lock_for_ticks_deadline() {
+        use crate::SpinLock;
+        use core::cell::Cell;
+
+        let lock = SpinLock::new(0u8);
+        let ticks = Cell::new(0u64);
+        let clock = || {
+            ticks.set(ticks.get() + 1);
+            ticks.get()
+        };
+
+        // Free lock: acquired on the first attempt, before the clock is consulted.
+        assert!(lock.try_lock_for_ticks(0, &clock).is_some());
+        assert_eq!(ticks.get(), 0, "Clock should not be read when the lock is free");
+
+        // Held lock: gives up once the fake clock passes the deadline.
+        let _held = lock.lock();
+        assert!(lock.try_lock_for_ticks(3, &clock).is_none());
+        assert_eq!(ticks.get(), 3, "Should stop at the first sample past the deadline");
+    }
+
+    #[test]
+    fn test_peek_never_waits_and_releases() {
+        use crate::SpinLock;
+
+        let lock = SpinLock::new(7u64);
+        assert_eq!(lock.peek(), Some(7));
+        assert!(!lock.is_locked(), "peek should release the lock immediately");
+
+        let guard = lock.lock();
+        assert_eq!(lock.peek(), None, "peek should not read data owned by a holder");
+        drop(guard);
+
+        assert_eq!(lock.peek(), Some(7));
+    }
+
+    #[cfg(all(feature = "metrics", feature = "std"))]
+    #[test]
+    fn test_spin_count_reports_contention() {
+        use crate::SpinLock;
+        use std::sync::Arc;
+        use std::thread;
+        use std::time::Duration;
+
+        let lock = Arc::new(SpinLock::new(()));
+        assert_eq!(lock.lock().spin_count(), 0, "Uncontended lock should not spin");
+        assert_eq!(lock.try_lock().unwrap().spin_count(), 0);
+
+        let held = lock.lock();
+        let waiter = {
+            let lock = lock.clone();
+            thread::spawn(move || lock.lock().spin_count())
+        };
+        thread::sleep(Duration::from_millis(20));
+        drop(held);
+
+        assert!(waiter.join().unwrap() > 0, "Contended lock should report spins");
+    }
+
+    #[cfg(feature = "parking")]
+    #[test]
+    fn test_lock_bounded_parks_and_wakes() {
+        use crate::SpinLock;
+        use std::sync::Arc;
+        use std::thread;
+        use std::time::Duration;
+
+        let lock = Arc::new(SpinLock::new(0usize));
+
+        // A long hold forces the waiter past its spin budget and into parking.
+        let held = lock.lock();
+        let waiter = {
+            let lock = lock.clone();
+            thread::spawn(move || *lock.lock_bounded() += 1)
+        };
+        thread::sleep(Duration::from_millis(100));
+        drop(held);
+        waiter.join().unwrap();
+
+        // Mixed spinning and parking acquisitions must never lose a wakeup.
+        let mut handles = vec![];
+        for i in 0..8 {
+            let lock = lock.clone();
+            handles.push(thread::spawn(move || {
+                for _ in 0..2_000 {
+                    if i % 2 == 0 {
+                        *lock.lock_bounded() += 1;
+                    } else {
+                        *lock.lock() += 1;
+                    }
+                }
+            }));
+        }
+        for h in handles {
+            h.join().unwrap();
+        }
+
+        assert_eq!(*lock.lock(), 1 + 8 * 2_000);
+    }
+
+    #[test]
+    fn test_is_locked_relaxed_tracks_state() {
+        use crate::SpinLock;
+
+        let lock = SpinLock::new(());
+        assert!(!lock.is_locked_relaxed());
+
+        let guard = lock.lock();
+        assert!(lock.is_locked_relaxed());
+        drop(guard);
+
+        assert!(!lock.is_locked_relaxed());
+    }
+
+    #[cfg(feature = "deadlock-detection")]
+    #[test]
+    #[should_panic(expected = "deadlock detected")]
+    fn test_deadlock_detection_catches_relock() {
+        use crate::SpinLock;
+
+        let lock = SpinLock::new(0);
+        let _first = lock.lock();
+        let _second = lock.lock();
+    }
+
+    #[cfg(feature = "deadlock-detection")]
+    #[test]
+    fn test_deadlock_detection_allows_cross_thread_contention() {
+        use crate::SpinLock;
+        use std::sync::Arc;
+        use std::thread;
+
+        let lock = Arc::new(SpinLock::new(0usize));
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let lock = lock.clone();
+                thread::spawn(move || {
+                    for _ in 0..5_000 {
+                        *lock.lock() += 1;
+                    }
+                })
+            })
+            .collect();
+        for h in handles {
+            h.join().expect("cross-thread contention must not trip the detector");
+        }
+
+        // Relocking after release on the same thread is fine too.
+        drop(lock.lock());
+        assert_eq!(*lock.lock(), 4 * 5_000);
+    }
+
+    #[test]
+    fn test_try_get_mut_manual_unlock() {
+        use crate::SpinLock;
+
+        let lock = SpinLock::new([0u8; 4]);
+        let data = lock.try_get_mut().expect("lock should be free");
+        data[0] = 7;
+        // Simulate handing the buffer to C code through a raw pointer.
+        let ptr = data.as_mut_ptr();
+        unsafe { *ptr.add(1) = 9 };
+
+        assert!(lock.try_get_mut().is_none(), "Lock should stay held until unlock");
+        assert!(lock.try_lock().is_none());
+
+        unsafe { lock.unlock() };
+        assert!(!lock.is_locked());
+        assert_eq!(*lock.lock(), [7, 9, 0, 0]);
+    }
+
+    #[test]
+    fn test_into_inner_get_mut_and_leak() {
+        use crate::spinlock::SpinGuard;
+        use crate::SpinLock;
+
+        let mut lock = SpinLock::new(vec![1]);
+        lock.get_mut().push(2);
+        assert_eq!(lock.into_inner(), vec![1, 2]);
+
+        let lock = SpinLock::new(5);
+        let leaked = SpinGuard::leak(lock.lock());
+        *leaked += 1;
+        assert!(lock.is_locked(), "Leaked guard should keep the lock held");
+        unsafe { lock.unlock() };
+        assert_eq!(*lock.lock(), 6);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_wait_until_unlocked_waits_for_holder() {
+        use crate::SpinLock;
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+        use std::thread;
+        use std::time::Duration;
+
+        let lock = Arc::new(SpinLock::new(0));
+        let released = Arc::new(AtomicBool::new(false));
+
+        let guard = lock.lock();
+        assert!(!lock.wait_until_unlocked_for(4), "Lock is held, bounded wait should fail");
+
+        let holder = {
+            let (lock, released) = (lock.clone(), released.clone());
+            thread::spawn(move || {
+                // Move the held state to this thread, then release after a delay.
+                let mut guard = lock.lock();
+                *guard = 1;
+                thread::sleep(Duration::from_millis(20));
+                released.store(true, Ordering::Relaxed);
+            })
+        };
+        drop(guard);
+        while !lock.is_locked() && !holder.is_finished() {
+            thread::yield_now();
+        }
+
+        lock.wait_until_unlocked();
+        assert!(released.load(Ordering::Relaxed), "Returned before the holder released");
+        assert!(!lock.is_locked(), "Waiting must not acquire the lock");
+        assert_eq!(*lock.lock(), 1);
+        holder.join().unwrap();
+    }
+
+    #[test]
+    fn test_id_and_pointer_formatting() {
+        use crate::SpinLock;
+
+        let locks = [SpinLock::new(1u8), SpinLock::new(2u8)];
+        assert_ne!(locks[0].id(), locks[1].id(), "Distinct live locks need distinct ids");
+        assert_eq!(locks[0].id(), &locks[0] as *const _ as usize);
+        assert_eq!(locks[0].id(), locks[0].id(), "Id should be stable");
+
+        let printed = format!("{:p}", locks[1]);
+        assert_eq!(printed, format!("{:p}", &locks[1] as *const SpinLock<u8>));
+    }
+
+    #[test]
+    fn test_try_lock_err_reports_would_block() {
+        use crate::spinlock::TryLockError;
+        use crate::SpinLock;
+
+        let lock = SpinLock::new(3);
+        {
+            let _held = lock.try_lock_err().expect("free lock should be acquired");
+            let err = lock.try_lock_err().err().expect("held lock must not be acquired");
+            assert_eq!(err, TryLockError::WouldBlock);
+            assert_eq!(err.to_string(), "try_lock failed because the operation would block");
+        }
+        assert_eq!(lock.try_lock_err().map(|g| *g), Ok(3));
+    }
+
+    #[test]
+    fn test_lock_pinned_polls_stored_future() {
+        use crate::SpinLock;
+        use core::future::Future;
+        use core::marker::PhantomPinned;
+        use core::pin::{pin, Pin};
+        use core::task::{Context, Poll, Waker};
+
+        // A `!Unpin` future that is pending on its first poll.
+        struct YieldOnce {
+            polled: bool,
+            _pin: PhantomPinned,
+        }
+        impl Future for YieldOnce {
+            type Output = u32;
+            fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<u32> {
+                // Safety: `polled` is not structurally pinned.
+                let this = unsafe { self.get_unchecked_mut() };
+                if this.polled {
+                    Poll::Ready(42)
+                } else {
+                    this.polled = true;
+                    Poll::Pending
+                }
+            }
+        }
+
+        let lock = pin!(SpinLock::new(YieldOnce {
+            polled: false,
+            _pin: PhantomPinned,
+        }));
+        let mut cx = Context::from_waker(Waker::noop());
+
+        let mut guard = unsafe { lock.as_ref().lock_pinned() };
+        assert_eq!(guard.as_mut().poll(&mut cx), Poll::Pending);
+        assert!(lock.is_locked());
+        drop(guard);
+
+        let mut guard = unsafe { lock.as_ref().lock_pinned() };
+        assert_eq!(guard.as_mut().poll(&mut cx), Poll::Ready(42));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_new_locked_initialization_handshake() {
+        use crate::SpinLock;
+        use std::sync::Arc;
+        use std::thread;
+        use std::time::Duration;
+
+        let config = Arc::new(SpinLock::new_locked(Vec::new()));
+        assert!(config.is_locked(), "Lock should start held");
+        assert!(config.try_lock().is_none());
+
+        let readers: Vec<_> = (0..3)
+            .map(|_| {
+                let config = config.clone();
+                thread::spawn(move || config.lock().clone())
+            })
+            .collect();
+
+        let mut setup = unsafe { config.assume_locked() };
+        thread::sleep(Duration::from_millis(10));
+        setup.extend([1, 2, 3]);
+        drop(setup);
+
+        for reader in readers {
+            assert_eq!(reader.join().unwrap(), vec![1, 2, 3], "Readers saw partial setup");
+        }
+        assert!(!config.is_locked());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_numeric_helpers_under_concurrency() {
+        use crate::SpinLock;
+        use std::sync::Arc;
+        use std::thread;
+
+        let counter = Arc::new(SpinLock::new(0i64));
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let counter = counter.clone();
+                thread::spawn(move || {
+                    let mut seen = Vec::new();
+                    for _ in 0..1_000 {
+                        counter.add(3);
+                        counter.sub(1);
+                        seen.push(counter.fetch_add(0));
+                    }
+                    counter.add(i);
+                    seen
+                })
+            })
+            .collect();
+
+        for h in handles {
+            let seen = h.join().unwrap();
+            // Every thread's net contribution is never negative, so after its own
+            // `k`-th add/sub pair the counter holds at least `2 * k`.
+            for (k, &v) in (1..).zip(&seen) {
+                assert!(v >= 2 * k, "iteration {k} saw {v}, below its own progress");
+            }
+        }
+        assert_eq!(*counter.lock(), 8 * 1_000 * 2 + (0..8).sum::<i64>());
+        assert_eq!(counter.fetch_add(5), 16_028);
+        assert_eq!(*counter.lock(), 16_033);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_try_lock_for_catches_flickering_lock() {
+        use crate::SpinLock;
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+        use std::thread;
+
+        // Several threads keep the lock busy with very short holds.
+        let lock = Arc::new(SpinLock::new(0u64));
+        let stop = Arc::new(AtomicBool::new(false));
+        let churners: Vec<_> = (0..3)
+            .map(|_| {
+                let (lock, stop) = (lock.clone(), stop.clone());
+                thread::spawn(move || {
+                    while !stop.load(Ordering::Relaxed) {
+                        *lock.lock() += 1;
+                    }
+                })
+            })
+            .collect();
+
+        let mut acquired = 0;
+        for _ in 0..200 {
+            if let Some(mut guard) = lock.try_lock_for(1_000) {
+                *guard += 1;
+                acquired += 1;
+            }
+        }
+        stop.store(true, Ordering::Relaxed);
+        for c in churners {
+            c.join().unwrap();
+        }
+        assert!(acquired > 0, "try_lock_for never caught a release of a flickering lock");
+    }
+
+    #[test]
+    fn test_with_field_projects_and_unlocks() {
+        use crate::SpinLock;
+
+        struct Big {
+            name: String,
+            values: [u32; 8],
+        }
+
+        let lock = SpinLock::new(Big {
+            name: String::from("big"),
+            values: [0; 8],
+        });
+        let sum = lock.with_field(
+            |big| &mut big.values[2..4],
+            |slice| {
+                slice.fill(5);
+                slice.iter().sum::<u32>()
+            },
+        );
+        assert_eq!(sum, 10);
+        assert!(!lock.is_locked(), "Lock should be released after with_field");
+
+        let guard = lock.lock();
+        assert_eq!(guard.values, [0, 0, 5, 5, 0, 0, 0, 0]);
+        assert_eq!(guard.name, "big");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_try_lock_for_counted_reports_attempts() {
+        use crate::SpinLock;
+        use std::sync::Arc;
+        use std::thread;
+        use std::time::Duration;
+
+        let lock = Arc::new(SpinLock::new(()));
+        let (_, used) = lock.try_lock_for_counted(4).expect("free lock");
+        assert_eq!(used, 1);
+
+        let holder = {
+            let lock = lock.clone();
+            thread::spawn(move || {
+                let _held = lock.lock();
+                thread::sleep(Duration::from_millis(20));
+            })
+        };
+        while !lock.is_locked() {
+            thread::yield_now();
+        }
+        assert_eq!(lock.try_lock_for_counted(6).err(), Some(6));
+        assert!(lock.try_lock_for_counted(0).is_err(), "Zero budget never acquires");
+
+        let (_, used) = lock.try_lock_for_counted(usize::MAX).expect("eventually released");
+        assert!(used > 1, "Acquisition after a release should take several attempts");
+        holder.join().unwrap();
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_bump_lets_waiter_in() {
+        use crate::SpinLock;
+        use std::sync::Arc;
+        use std::thread;
+
+        let lock = Arc::new(SpinLock::new(Vec::new()));
+        let mut guard = lock.lock();
+
+        let waiter = {
+            let lock = lock.clone();
+            thread::spawn(move || lock.lock().push("waiter"))
+        };
+
+        // Keep bumping until the waiter got its turn mid-section.
+        while !guard.contains(&"waiter") {
+            guard.push("holder");
+            guard.bump();
+        }
+        assert!(lock.is_locked(), "bump must reacquire before returning");
+        drop(guard);
+        waiter.join().unwrap();
+        assert!(!lock.is_locked());
+    }
+
+    #[test]
+    fn test_reinit_drops_old_value_under_lock() {
+        use crate::SpinLock;
+        use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+        static DROPPED_LOCKED: AtomicBool = AtomicBool::new(false);
+        static DROPPED: AtomicU32 = AtomicU32::new(0);
+        static LOCK: SpinLock<Tracked> = SpinLock::new(Tracked(1));
+
+        struct Tracked(u32);
+        impl Drop for Tracked {
+            fn drop(&mut self) {
+                if self.0 == 1 {
+                    DROPPED_LOCKED.store(LOCK.is_locked(), Ordering::Relaxed);
+                }
+                DROPPED.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        LOCK.reinit(|| Tracked(2));
+        assert_eq!(DROPPED.load(Ordering::Relaxed), 1, "Old value should be dropped once");
+        assert!(DROPPED_LOCKED.load(Ordering::Relaxed), "Old value dropped outside the lock");
+        assert_eq!(LOCK.lock().0, 2);
+        assert!(!LOCK.is_locked());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_set_and_replace_do_not_tear() {
+        use crate::SpinLock;
+        use std::sync::Arc;
+        use std::thread;
+
+        // Every stored pair keeps both halves equal; a torn write would break that.
+        let lock = Arc::new(SpinLock::new((0u64, 0u64)));
+        let writers: Vec<_> = (0..4)
+            .map(|t| {
+                let lock = lock.clone();
+                thread::spawn(move || {
+                    for i in 0..2_000 {
+                        let v = t * 10_000 + i;
+                        if i % 2 == 0 {
+                            lock.set((v, v));
+                        } else {
+                            let (a, b) = lock.replace((v, v));
+                            assert_eq!(a, b, "replace returned a torn value");
+                        }
+                    }
+                })
+            })
+            .collect();
+        for _ in 0..2_000 {
+            let (a, b) = *lock.lock();
+            assert_eq!(a, b, "lock observed a torn value");
+        }
+        for w in writers {
+            w.join().unwrap();
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_drain_locked_holds_lock_only_for_swap() {
+        use crate::SpinLock;
+        use std::sync::Arc;
+        use std::thread;
+
+        let events = Arc::new(SpinLock::new(Vec::new()));
+        let producer = {
+            let events = events.clone();
+            thread::spawn(move || {
+                for i in 0..1_000u32 {
+                    events.lock().push(i);
+                }
+            })
+        };
+        let mut seen = Vec::new();
+        while seen.len() < 1_000 {
+            seen.extend(events.drain_locked());
+        }
+        producer.join().unwrap();
+        assert_eq!(seen, (0..1_000).collect::<Vec<_>>(), "Every event drained once, in order");
+
+        // Relocking while processing would spin forever if the lock were still held.
+        events.lock().extend([1, 2, 3]);
+        for event in events.drain_locked() {
+            assert!(!events.is_locked());
+            events.lock().push(event * 10);
+        }
+        assert_eq!(*events.lock(), vec![10, 20, 30]);
+    }
+
+    #[test]
+    fn test_try_attempts_is_exact_and_tight() {
+        use crate::SpinLock;
+
+        let lock = SpinLock::new(());
+        assert!(lock.try_attempts(0).is_none(), "Zero attempts never acquire");
+
+        let held = lock.try_attempts(1).expect("free lock on the first attempt");
+        assert!(lock.try_attempts(1_000).is_none());
+        drop(held);
+        assert!(lock.try_attempts(1).is_some());
+    }
+
+    #[cfg(feature = "parking")]
+    #[test]
+    fn test_parked_waiters_are_always_woken() {
+        use crate::SpinLock;
+        use core::sync::atomic::Ordering;
+        use std::sync::Arc;
+        use std::thread;
+        use std::time::Duration;
+
+        let lock = Arc::new(SpinLock::new(0u32));
+        // Holds long enough to push the others past their spin budget: every
+        // release must find and wake a parked waiter, or a thread hangs here.
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let lock = lock.clone();
+                thread::spawn(move || {
+                    for _ in 0..50 {
+                        let mut guard = lock.lock_bounded();
+                        *guard += 1;
+                        thread::sleep(Duration::from_micros(200));
+                    }
+                })
+            })
+            .collect();
+        for h in handles {
+            h.join().unwrap();
+        }
+        assert_eq!(*lock.lock(), 200);
+        assert_eq!(lock.parking.waiters.load(Ordering::Relaxed), 0, "Waiter count leaked");
+    }
+
+    #[test]
+    fn test_get_unchecked_single_threaded() {
+        use crate::SpinLock;
+
+        static BOOT_CONFIG: SpinLock<[u32; 2]> = SpinLock::new([0; 2]);
+
+        // Single-threaded "boot" phase: nothing else can observe the data yet.
+        unsafe {
+            let config = BOOT_CONFIG.get_unchecked_mut();
+            config[0] = 115_200;
+            config[1] = 8;
+            assert_eq!(*BOOT_CONFIG.get_unchecked(), [115_200, 8]);
+        }
+        assert!(!BOOT_CONFIG.is_locked(), "Unchecked access must not touch the lock");
+        assert_eq!(*BOOT_CONFIG.lock(), [115_200, 8]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_partial_eq_locks_in_address_order() {
+        use crate::SpinLock;
+        use std::sync::Arc;
+        use std::thread;
+
+        let pair = Arc::new((SpinLock::new(1u32), SpinLock::new(1u32)));
+        assert!(pair.0 == pair.0, "A lock equals itself without deadlocking");
+        assert!(pair.0 == pair.1);
+
+        // Opposite comparison orders on the same pair must not deadlock.
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let pair = pair.clone();
+                thread::spawn(move || {
+                    for _ in 0..5_000 {
+                        if i % 2 == 0 {
+                            assert!(pair.0 == pair.1);
+                        } else {
+                            assert!(pair.1 == pair.0);
+                        }
+                    }
+                })
+            })
+            .collect();
+        for h in handles {
+            h.join().unwrap();
+        }
+
+        pair.1.set(2);
+        assert!(pair.0 != pair.1);
+    }
+
+    #[test]
+    fn test_owns_matches_only_its_lock() {
+        use crate::SpinLock;
+
+        let first = SpinLock::new(1);
+        let second = SpinLock::new(1);
+        let guard = second.lock();
+        assert!(!first.owns(&guard), "Guard from a different lock must not match");
+        assert!(second.owns(&guard));
+
+        let first_guard = first.try_lock().unwrap();
+        assert!(first.owns(&first_guard));
+    }
+
+    #[cfg(all(feature = "debug-guard-token", feature = "std"))]
+    #[test]
+    fn test_guard_token_detects_stale_drop() {
+        use crate::SpinLock;
+        use core::mem::ManuallyDrop;
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let lock = SpinLock::new(0);
+
+        // Misuse: keep a guard alive past a manual unlock...
+        let mut stale = ManuallyDrop::new(lock.lock());
+        unsafe { lock.unlock() };
+        // ...while someone else acquires the lock legitimately.
+        let current = lock.lock();
+
+        let outcome = catch_unwind(AssertUnwindSafe(|| unsafe { ManuallyDrop::drop(&mut stale) }));
+        assert_eq!(outcome.is_err(), cfg!(debug_assertions), "debug_assert should fire");
+        assert!(lock.is_locked(), "Stale drop must not release the current holder");
+
+        drop(current);
+        assert!(!lock.is_locked());
+
+        // Bumping keeps a live guard's token current.
+        let mut guard = lock.lock();
+        guard.bump();
+        drop(guard);
+        assert!(!lock.is_locked(), "Bumped guard should still release on drop");
+    }
+
+    #[test]
+    fn test_lock_shared_ref_is_exclusive_read_only() {
+        use crate::SpinLock;
+
+        let lock = SpinLock::new(String::from("config"));
+        {
+            let view = lock.lock_shared_ref();
+            assert_eq!(view.as_str(), "config");
+            assert!(lock.try_lock().is_none(), "Read-only guard still holds the lock");
+        }
+        assert!(!lock.is_locked(), "Dropping the read guard releases the lock");
+    }
+
+    #[test]
+    fn test_repr_c_layout() {
+        use crate::SpinLock;
+        use core::mem::{offset_of, size_of};
+        use core::sync::atomic::{AtomicBool, Ordering::Relaxed};
+
+        assert_eq!(offset_of!(SpinLock<u32>, data), 0);
+        assert_eq!(offset_of!(SpinLock<u32>, raw), size_of::<u32>());
+        assert_eq!(SpinLock::<u64>::LOCKED_OFFSET, 8);
+        #[cfg(not(any(
+            feature = "adaptive",
+            feature = "contention-hint",
+            feature = "parking",
+            feature = "deadlock-detection",
+            feature = "profiling",
+            feature = "debug-guard-token"
+        )))]
+        {
+            // data, flag, config byte, backoff start.
+            assert_eq!(size_of::<SpinLock<u32>>(), 12);
+            assert_eq!(size_of::<SpinLock<u8>>(), 8);
+            assert_eq!(core::mem::align_of::<SpinLock<u32>>(), 4);
+        }
+
+        // Cooperate on the flag the way C code would: through its raw offset.
+        let lock = SpinLock::new(7u32);
+        let base = &lock as *const SpinLock<u32> as *const u8;
+        let flag = unsafe { &*(base.add(SpinLock::<u32>::LOCKED_OFFSET) as *const AtomicBool) };
+        let guard = lock.lock();
+        assert!(flag.load(Relaxed));
+        drop(guard);
+        assert!(!flag.load(Relaxed));
+        assert_eq!(unsafe { *(base as *const u32) }, 7);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_init_at_in_place() {
+        use crate::SpinLock;
+        use std::mem::MaybeUninit;
+        use std::thread;
+
+        // A heap region standing in for a shared-memory mapping.
+        let mut region: Box<MaybeUninit<SpinLock<[u32; 8]>>> = Box::new(MaybeUninit::uninit());
+        unsafe { SpinLock::init_at(region.as_mut_ptr(), [0; 8]) };
+        let lock = unsafe { region.assume_init_ref() };
+        assert!(!lock.is_locked());
+
+        thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| {
+                    for _ in 0..1_000 {
+                        lock.lock().iter_mut().for_each(|v| *v += 1);
+                    }
+                });
+            }
+        });
+        assert_eq!(*lock.lock(), [4_000; 8]);
+        unsafe { region.assume_init_drop() };
+    }
+
+    #[test]
+    fn test_try_lock_or_else_branches() {
+        use crate::SpinLock;
+
+        let lock = SpinLock::new(10u32);
+        let got = lock.try_lock_or_else(|v| { *v += 1; *v }, || 0);
+        assert_eq!(got, 11);
+        assert!(!lock.is_locked());
+
+        let held = lock.lock();
+        let mut fallback_ran = false;
+        let got = lock.try_lock_or_else(|v| *v, || { fallback_ran = true; u32::MAX });
+        assert!(fallback_ran);
+        assert_eq!(got, u32::MAX);
+        assert!(lock.is_locked());
+        drop(held);
+        assert_eq!(*lock.lock(), 11);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_compare_and_set_contended_transition() {
+        use crate::SpinLock;
+        use std::sync::Barrier;
+        use std::thread;
+
+        // Two threads race each transition 0 -> 1 -> ... ; exactly one wins each.
+        let state = SpinLock::new(0u32);
+        let barrier = Barrier::new(2);
+        let wins: Vec<u32> = thread::scope(|s| {
+            let handles: Vec<_> = (0..2)
+                .map(|_| {
+                    s.spawn(|| {
+                        let mut won = 0;
+                        for from in 0..1_000 {
+                            barrier.wait();
+                            if state.compare_and_set(&from, from + 1) {
+                                won += 1;
+                            }
+                            barrier.wait();
+                        }
+                        won
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        assert_eq!(wins.iter().sum::<u32>(), 1_000);
+        assert_eq!(state.into_inner(), 1_000);
+    }
+
+    #[test]
+    fn test_view_projects_and_releases() {
+        use crate::SpinLock;
+
+        let lock = SpinLock::new(String::from("spin"));
+        let upper = lock.view(|s| s.to_uppercase());
+        assert_eq!(upper, "SPIN");
+        assert!(!lock.is_locked());
+        assert_eq!(lock.view(String::len), 4);
+    }
+
+    #[test]
+    fn test_into_guarded_round_trip() {
+        use crate::SpinLock;
+
+        let mut guard = SpinLock::new(vec![1u8]).into_guarded();
+        guard.push(2);
+        assert_eq!(guard.len(), 2);
+
+        // Back to a regular lock, which was never marked held.
+        let lock = guard.into_lock();
+        assert!(!lock.is_locked());
+        lock.lock().push(3);
+        assert_eq!(lock.into_guarded().into_inner(), [1, 2, 3]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_lock_interruptible_during_contention() {
+        use crate::{BackOff, SpinLock};
+        use std::sync::atomic::{AtomicBool, Ordering::Release};
+        use std::thread;
+        use std::time::Duration;
+
+        let lock = SpinLock::new(0u32);
+        let irq = AtomicBool::new(false);
+        let held = lock.lock();
+
+        thread::scope(|s| {
+            let waiter = s.spawn(|| {
+                let backoff = BackOff::new();
+                let got = lock.lock_interruptible_with(&irq, &backoff).is_some();
+                (got, backoff.current())
+            });
+            thread::sleep(Duration::from_millis(10));
+            irq.store(true, Release);
+            let (got, after) = waiter.join().unwrap();
+            assert!(!got, "acquired a lock that was held throughout");
+            // The wait escalated, then the abort relaxed it by one step.
+            assert!(after > BackOff::START_VALUE);
+        });
+
+        // Relax-on-abort: the reached spin count is halved.
+        let escalated = BackOff::new_with(1 << 12);
+        assert!(lock.lock_interruptible_with(&irq, &escalated).is_none());
+        assert_eq!(escalated.current(), 1 << 11);
 
-        // Attempt to acquire again (should fail)
-        assert!(lock.try_lock_for(10).is_none(), "Lock should not be acquirable while held");
+        drop(held);
+        irq.store(false, Release);
+        assert!(lock.lock_interruptible(&irq).is_some());
+    }
 
-        // After drop, should succeed
-        drop(_guard);
-        let guard2 = lock.try_lock_for(1000);
-        assert!(guard2.is_some(), "Lock should succeed after previous guard drop");
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_low_latency_lock_excludes() {
+        use crate::SpinLock;
+        use std::thread;
+
+        let lock = SpinLock::new_low_latency(0u64);
+        thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| {
+                    for _ in 0..10_000 {
+                        *lock.lock() += 1;
+                    }
+                });
+            }
+        });
+        assert_eq!(lock.into_inner(), 40_000);
+    }
+
+    #[test]
+    fn test_session_leave_and_drop_release() {
+        use crate::SpinLock;
+
+        let lock = SpinLock::new(0u32);
+        let mut session = lock.enter();
+        *session.data() += 1;
+        assert!(lock.is_locked());
+        session.leave();
+        assert!(!lock.is_locked());
+
+        {
+            let mut forgotten = lock.enter();
+            *forgotten.data() += 1;
+        } // no `leave`: dropping releases
+        assert!(!lock.is_locked());
+        assert_eq!(*lock.lock(), 2);
+    }
+
+    #[test]
+    fn test_try_lock_for_with_keeps_backoff_state() {
+        use crate::{BackOff, SpinLock};
+
+        let lock = SpinLock::new(());
+        let backoff = BackOff::new();
+        let held = lock.lock();
+
+        assert!(lock.try_lock_for_with(3, &backoff).is_none());
+        let after_first = backoff.current();
+        assert_eq!(after_first, BackOff::START_VALUE << 3);
+        assert!(lock.try_lock_for_with(3, &backoff).is_none());
+        assert_eq!(backoff.current(), after_first << 3);
+
+        drop(held);
+        assert!(lock.try_lock_for_with(3, &backoff).is_some());
+        // Success leaves the state to the caller.
+        assert_eq!(backoff.current(), after_first << 3);
+    }
+
+    #[cfg(feature = "deadlock-detection")]
+    #[test]
+    fn test_try_lock_diagnostic_reports_holder() {
+        use crate::SpinLock;
+        use std::sync::Barrier;
+        use std::thread;
+
+        let lock = SpinLock::new(0u32);
+        let held = Barrier::new(2);
+        let checked = Barrier::new(2);
+
+        thread::scope(|s| {
+            let holder = s.spawn(|| {
+                let _guard = lock.lock();
+                held.wait();
+                checked.wait();
+            });
+            held.wait();
+            let reported = lock.try_lock_diagnostic().err().unwrap();
+            assert_eq!(reported, Some(holder.thread().id()));
+            checked.wait();
+        });
+
+        assert!(lock.try_lock_diagnostic().is_ok());
+    }
+
+    #[cfg(feature = "adaptive")]
+    #[test]
+    fn test_adaptive_budget_converges() {
+        use crate::SpinLock;
+
+        let lock = SpinLock::new(());
+        assert_eq!(lock.adaptive_spin_budget(), 1);
+
+        // Acquisitions that keep taking 10 rounds pull the average to 10.
+        for _ in 0..200 {
+            lock.record_spin_rounds(10);
+        }
+        let budget = lock.adaptive_spin_budget();
+        assert!((19..=21).contains(&budget), "budget = {budget}");
+
+        // Uncontended acquisitions (through the real `lock`) decay it again.
+        for _ in 0..200 {
+            drop(lock.lock());
+        }
+        assert_eq!(lock.adaptive_spin_budget(), 1);
+    }
+
+    /// Ensures that the adaptive yield threshold scales with the lock's own backoff start.
+    #[cfg(feature = "adaptive")]
+    #[test]
+    fn test_adaptive_threshold_follows_backoff_start() {
+        use crate::{BackOff, SpinLock};
+
+        // A fresh budget of one round: yield once past the first round.
+        let default = SpinLock::new(());
+        assert_eq!(default.lock_backoff().yield_threshold(), BackOff::START_VALUE << 1);
+        let hot = SpinLock::new_with_backoff((), BackOff::START_VALUE << 3);
+        assert_eq!(hot.lock_backoff().yield_threshold(), BackOff::START_VALUE << 4);
+
+        // A large budget still yields no later than a default `BackOff`...
+        for _ in 0..200 {
+            hot.record_spin_rounds(10);
+        }
+        assert_eq!(hot.lock_backoff().yield_threshold(), BackOff::YIELD_THRESHOLD);
+
+        // ...and a start past that threshold yields from its first round.
+        let hottest = SpinLock::new_with_backoff((), BackOff::YIELD_THRESHOLD << 2);
+        let backoff = hottest.lock_backoff();
+        assert!(backoff.current() > backoff.yield_threshold());
+        drop(hottest.lock());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_lock_two_opposite_orders() {
+        use crate::SpinLock;
+        use std::thread;
+
+        let left = SpinLock::new(1_000i64);
+        let right = SpinLock::new(1_000i64);
+
+        // Classic transfer deadlock: each thread names the locks in opposite order.
+        thread::scope(|s| {
+            s.spawn(|| {
+                for _ in 0..10_000 {
+                    let (mut a, mut b) = SpinLock::lock_two(&left, &right);
+                    *a -= 1;
+                    *b += 1;
+                }
+            });
+            s.spawn(|| {
+                for _ in 0..10_000 {
+                    let (mut b, mut a) = SpinLock::lock_two(&right, &left);
+                    *b -= 1;
+                    *a += 1;
+                }
+            });
+        });
+        assert_eq!((left.into_inner(), right.into_inner()), (1_000, 1_000));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_lock_two_nested_and_same() {
+        use crate::SpinLock;
+
+        // The inner lock lives at the outer lock's address.
+        let nested = SpinLock::new(SpinLock::new(5u8));
+        let inner = unsafe { &*nested.data_ptr() };
+        assert_eq!(nested.id(), inner.id());
+        let (outer, mut value) = SpinLock::lock_two(&nested, inner);
+        *value += 1;
+        drop((outer, value));
+        assert_eq!(*nested.lock().lock(), 6);
+
+        let lock = SpinLock::new(0);
+        let same = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            drop(SpinLock::lock_two(&lock, &lock));
+        }));
+        assert!(same.is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_lock_when_consumer_waits_for_producer() {
+        use crate::SpinLock;
+        use std::thread;
+        use std::time::Duration;
+
+        let queue = SpinLock::new(Vec::<u32>::new());
+        assert!(queue.lock_when_for(4, |q| !q.is_empty()).is_none());
+        assert!(!queue.is_locked());
+
+        thread::scope(|s| {
+            s.spawn(|| {
+                for i in 0..100 {
+                    queue.lock().push(i);
+                    if i % 10 == 0 {
+                        thread::sleep(Duration::from_millis(1));
+                    }
+                }
+            });
+            let mut consumed = 0;
+            while consumed < 100 {
+                let mut items = queue.lock_when(|q| !q.is_empty());
+                assert!(!items.is_empty());
+                consumed += items.drain(..).count();
+            }
+        });
+
+        queue.lock().push(7);
+        assert_eq!(queue.lock_when_for(0, |q| q.len() == 1).map(|q| q[0]), Some(7));
+    }
+
+    #[cfg(all(feature = "contention-hint", feature = "std"))]
+    #[test]
+    fn test_contention_hint_seeds_backoff() {
+        use crate::spinlock::MAX_CONTENTION_HINT;
+        use crate::{BackOff, SpinLock};
+        use std::thread;
+        use std::time::Duration;
+
+        let cold = SpinLock::new(0u32);
+        drop(cold.lock());
+        assert_eq!(cold.lock_backoff().current(), BackOff::START_VALUE);
+
+        // One really contended acquisition heats the lock up.
+        let hot = SpinLock::new(0u32);
+        thread::scope(|s| {
+            let held = hot.lock();
+            let waiter = s.spawn(|| *hot.lock() += 1);
+            thread::sleep(Duration::from_millis(5));
+            drop(held);
+            waiter.join().unwrap();
+        });
+        assert_eq!(hot.contention_hint(), 1);
+        assert!(hot.lock_backoff().current() > cold.lock_backoff().current());
+
+        // Saturates on the way up, decays on uncontended acquisitions.
+        for _ in 0..10 {
+            hot.record_contention(4);
+        }
+        assert_eq!(hot.contention_hint(), MAX_CONTENTION_HINT);
+        assert_eq!(hot.lock_backoff().current(), BackOff::START_VALUE << MAX_CONTENTION_HINT);
+        for _ in 0..MAX_CONTENTION_HINT {
+            drop(hot.lock());
+        }
+        assert_eq!(hot.contention_hint(), 0);
+    }
+
+    #[cfg(all(debug_assertions, feature = "std"))]
+    #[test]
+    fn test_debug_assert_held() {
+        use crate::SpinLock;
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let lock = SpinLock::new(0u32);
+        let guard = lock.lock();
+        lock.debug_assert_held();
+        drop(guard);
+        assert!(catch_unwind(AssertUnwindSafe(|| lock.debug_assert_held())).is_err());
+
+        // With holder tracking, another thread holding it is not enough.
+        #[cfg(feature = "deadlock-detection")]
+        {
+            let held = std::sync::Barrier::new(2);
+            let release = std::sync::Barrier::new(2);
+            std::thread::scope(|s| {
+                s.spawn(|| {
+                    let _guard = lock.lock();
+                    held.wait();
+                    release.wait();
+                });
+                held.wait();
+                assert!(catch_unwind(AssertUnwindSafe(|| lock.debug_assert_held())).is_err());
+                release.wait();
+            });
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_lock_desync_salts_per_thread() {
+        use crate::spinlock::thread_salt;
+        use crate::SpinLock;
+        use std::collections::HashSet;
+        use std::thread;
+
+        // Stable within a thread, different across threads.
+        assert_eq!(thread_salt(), thread_salt());
+        let salts: HashSet<u32> = (0..8)
+            .map(|_| thread::spawn(thread_salt).join().unwrap())
+            .collect();
+        assert!(salts.len() > 1);
+
+        let lock = SpinLock::new(0u64);
+        thread::scope(|s| {
+            for _ in 0..8 {
+                s.spawn(|| {
+                    for _ in 0..2_000 {
+                        *lock.lock_desync() += 1;
+                    }
+                });
+            }
+        });
+        assert_eq!(lock.into_inner(), 16_000);
+    }
+
+    #[test]
+    fn test_clone_data_snapshot() {
+        use crate::SpinLock;
+
+        let lock = SpinLock::new(vec![1, 2]);
+        let mut guard = lock.lock();
+        let snapshot = guard.clone_data();
+        guard.push(3);
+        drop(guard);
+
+        assert_eq!(snapshot, [1, 2]);
+        assert_eq!(*lock.lock(), [1, 2, 3]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_assume_init_ref_then_shared() {
+        use crate::SpinLock;
+        use std::thread;
+
+        let table = SpinLock::new([0u32; 8]);
+        // Init phase: single writer, then unsynchronized reads.
+        for (i, slot) in table.lock().iter_mut().enumerate() {
+            *slot = i as u32;
+        }
+        let init = unsafe { table.assume_init_ref() };
+        assert_eq!(init.iter().sum::<u32>(), 28);
+        assert!(!table.is_locked(), "init reads never take the lock");
+
+        // Shared phase: spawned threads see the initialized data via lock().
+        thread::scope(|s| {
+            for t in 0..4 {
+                let table = &table;
+                s.spawn(move || {
+                    let mut guard = table.lock();
+                    assert_eq!(guard[t], t as u32);
+                    guard[t] += 10;
+                });
+            }
+        });
+        assert_eq!(table.lock().iter().sum::<u32>(), 68);
+    }
+
+    #[test]
+    fn test_try_lock_for_spin_bound() {
+        use crate::spinlock::TRY_LOCK_FOR_MAX_ROUND_SPINS;
+        use crate::SpinLock;
+
+        // The curve `try_lock_for` waits with: doubling, then flat at the cap.
+        let backoff = SpinLock::<()>::try_lock_for_backoff();
+        let mut total = 0u64;
+        for _ in 0..10 {
+            let round = backoff.current();
+            assert!(round <= TRY_LOCK_FOR_MAX_ROUND_SPINS);
+            total += u64::from(round);
+            backoff.wait();
+        }
+        assert!(total <= 10 * u64::from(TRY_LOCK_FOR_MAX_ROUND_SPINS), "total = {total}");
+        assert!(total < 4_000, "ten attempts no longer spin tens of thousands of times");
+
+        // And a held lock still gives up after exactly `spins` attempts.
+        let lock = SpinLock::new(0u8);
+        let _held = lock.lock();
+        assert_eq!(lock.try_lock_for_counted(10).err(), Some(10));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_hash_matches_inner_value() {
+        use crate::SpinLock;
+        use std::collections::HashSet;
+        use std::hash::{BuildHasher, RandomState};
+
+        let state = RandomState::new();
+        let (a, b) = (SpinLock::new("key"), SpinLock::new("key"));
+        assert_eq!(state.hash_one(&a), state.hash_one(&b));
+        assert_eq!(state.hash_one(&a), state.hash_one("key"));
+        assert!(!a.is_locked(), "hashing releases the lock");
+
+        // Interior mutability is the point of the type; the keys are never locked mutably.
+        #[allow(clippy::mutable_key_type)]
+        let keys: HashSet<SpinLock<u32>> = [1, 2, 2, 3].into_iter().map(SpinLock::new).collect();
+        assert_eq!(keys.len(), 3);
+        assert!(keys.contains(&SpinLock::new(2)));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_replace_if_keeps_newest_under_contention() {
+        use crate::SpinLock;
+        use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+        use std::thread;
+
+        let latest = SpinLock::new(0u64);
+        let (replaced, rejected) = (AtomicUsize::new(0), AtomicUsize::new(0));
+        thread::scope(|s| {
+            for t in 0..4u64 {
+                let (latest, replaced, rejected) = (&latest, &replaced, &rejected);
+                s.spawn(move || {
+                    // Interleaved timestamps: thread t offers t, t + 4, t + 8, ...
+                    for stamp in (t..2_000).step_by(4) {
+                        match latest.replace_if(|&current| stamp > current, stamp) {
+                            Ok(old) => {
+                                assert!(old < stamp);
+                                replaced.fetch_add(1, Relaxed);
+                            }
+                            Err(back) => {
+                                assert_eq!(back, stamp);
+                                rejected.fetch_add(1, Relaxed);
+                            }
+                        }
+                    }
+                });
+            }
+        });
+
+        assert_eq!(latest.into_inner(), 1_999);
+        assert_eq!(replaced.load(Relaxed) + rejected.load(Relaxed), 2_000);
+        assert!(replaced.load(Relaxed) > 0);
+        // Stamp 0 never beats the initial 0, so at least one offer is rejected.
+        assert!(rejected.load(Relaxed) > 0);
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_zeroizing_lock_wiped_on_drop() {
+        use crate::SpinLock;
+        use core::mem::MaybeUninit;
+        use zeroize::Zeroizing;
+
+        let mut slot = MaybeUninit::<SpinLock<Zeroizing<[u8; 32]>>>::uninit();
+        let lock = slot.write(SpinLock::new_zeroizing([0xA5; 32]));
+        let data = lock.lock().as_ptr();
+        assert_eq!(unsafe { *data }, 0xA5);
+
+        // Drop in place, then inspect the still-allocated bytes it occupied.
+        unsafe { slot.assume_init_drop() };
+        let bytes = unsafe { core::slice::from_raw_parts(data, 32) };
+        assert!(bytes.iter().all(|&b| b == 0), "secret left in memory: {bytes:?}");
+
+        let plain = SpinLock::new([1u8; 4]);
+        plain.zeroize();
+        assert_eq!(*plain.lock(), [0; 4]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_lock_weak_upgrade_and_dropped() {
+        use crate::spinlock::ArcSpinGuard;
+        use crate::SpinLock;
+        use std::sync::Arc;
+        use std::thread;
+
+        let shared = SpinLock::new_arc(vec![1]);
+        let weak = Arc::downgrade(&shared);
+
+        // Upgrade-then-lock: the guard keeps the lock alive and held.
+        let mut guard = SpinLock::lock_weak(&weak).unwrap();
+        assert!(shared.is_locked());
+        assert_eq!(Arc::strong_count(ArcSpinGuard::lock(&guard)), 2);
+        guard.push(2);
+        thread::spawn(move || drop(guard)).join().unwrap();
+        assert!(!shared.is_locked(), "dropping the guard elsewhere releases the lock");
+        assert_eq!(*shared.lock(), [1, 2]);
+
+        // A guard outliving every other strong reference still works.
+        let mut last = SpinLock::lock_weak(&weak).unwrap();
+        drop(shared);
+        last.push(3);
+        drop(last);
+
+        // Dropped target: nothing to lock.
+        assert!(SpinLock::lock_weak(&weak).is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_seqcst_release_store_buffering() {
+        use crate::SpinLock;
+        use std::sync::atomic::{fence, AtomicBool, Ordering::SeqCst};
+        use std::sync::Barrier;
+        use std::thread;
+
+        const ROUNDS: u32 = 2_000;
+
+        // Store buffering: the holder releases then reads `flag`; the other
+        // thread sets `flag`, fences, then looks at the lock. With a `SeqCst`
+        // release, at least one of them must see the other's write.
+        let lock = SpinLock::new_seqcst(0u32);
+        let flag = AtomicBool::new(false);
+        let holder_saw_flag = AtomicBool::new(false);
+        let (start, done) = (Barrier::new(2), Barrier::new(2));
+        thread::scope(|s| {
+            s.spawn(|| {
+                for round in 0..ROUNDS {
+                    let mut guard = lock.lock();
+                    *guard = round;
+                    start.wait();
+                    drop(guard);
+                    holder_saw_flag.store(flag.load(SeqCst), SeqCst);
+                    done.wait();
+                }
+            });
+            s.spawn(|| {
+                for round in 0..ROUNDS {
+                    flag.store(false, SeqCst);
+                    start.wait();
+                    flag.store(true, SeqCst);
+                    fence(SeqCst);
+                    let saw_locked = lock.is_locked();
+                    if !saw_locked {
+                        // Released, so the write under the lock is visible.
+                        assert_eq!(*lock.lock(), round);
+                    }
+                    done.wait();
+                    assert!(
+                        holder_saw_flag.load(SeqCst) || !saw_locked,
+                        "round {round}: both sides missed each other"
+                    );
+                }
+            });
+        });
+        assert!(!lock.is_locked());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_try_lock_never_fails_spuriously() {
+        use crate::SpinLock;
+        use std::sync::atomic::{AtomicUsize, Ordering::SeqCst};
+        use std::sync::Barrier;
+        use std::thread;
+
+        const THREADS: usize = 4;
+        const ROUNDS: usize = 500;
+
+        // Uncontended: a free lock is always taken.
+        let lock = SpinLock::new(0usize);
+        for _ in 0..10_000 {
+            assert!(lock.try_lock().is_some());
+        }
+
+        // Racing for a free lock: every `None` coincides with the one winner
+        // still holding it, so each round has exactly one success.
+        let winners = AtomicUsize::new(0);
+        let (start, decided, released) =
+            (Barrier::new(THREADS), Barrier::new(THREADS), Barrier::new(THREADS));
+        thread::scope(|s| {
+            for t in 0..THREADS {
+                let (lock, winners) = (&lock, &winners);
+                let (start, decided, released) = (&start, &decided, &released);
+                s.spawn(move || {
+                    for round in 0..ROUNDS {
+                        // Vary the arrival order a little from round to round.
+                        for i in 0..(round * 7 + t * 13) % 64 {
+                            core::hint::black_box(i);
+                        }
+                        start.wait();
+                        let guard = lock.try_lock();
+                        if let Some(mut guard) = guard {
+                            winners.fetch_add(1, SeqCst);
+                            *guard = t;
+                            decided.wait();
+                            drop(guard);
+                        } else {
+                            decided.wait();
+                        }
+                        if t == 0 {
+                            assert_eq!(winners.swap(0, SeqCst), 1, "round {round}");
+                        }
+                        released.wait();
+                    }
+                });
+            }
+        });
+        assert!(!lock.is_locked());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_option_slot_init_once_then_take() {
+        use crate::SpinLock;
+        use std::sync::atomic::{AtomicUsize, Ordering::SeqCst};
+        use std::thread;
+
+        let slot: SpinLock<Option<usize>> = SpinLock::new(None);
+        let calls = AtomicUsize::new(0);
+        for cycle in 0..3 {
+            let filled = AtomicUsize::new(0);
+            thread::scope(|s| {
+                for t in 0..4 {
+                    let (slot, calls, filled) = (&slot, &calls, &filled);
+                    s.spawn(move || {
+                        let inserted = slot.get_or_insert_with(|| {
+                            calls.fetch_add(1, SeqCst);
+                            cycle * 10 + t
+                        });
+                        if inserted {
+                            filled.fetch_add(1, SeqCst);
+                        }
+                    });
+                }
+            });
+            assert_eq!(filled.load(SeqCst), 1, "exactly one caller fills the slot");
+            assert_eq!(calls.load(SeqCst), cycle + 1, "f runs only on an empty slot");
+            let value = slot.take().expect("slot was filled");
+            assert_eq!(value / 10, cycle);
+            assert_eq!(slot.take(), None);
+        }
+        assert!(!slot.is_locked());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_try_lock_for_grabs_lock_freed_mid_round() {
+        use crate::{BackOff, SpinLock};
+        use std::sync::Barrier;
+        use std::thread;
+        use std::time::{Duration, Instant};
+
+        let lock = SpinLock::new(0u32);
+        // One full round at this step would spin for many seconds.
+        let backoff = BackOff::new_with(1 << 30);
+        let held = Barrier::new(2);
+
+        thread::scope(|s| {
+            s.spawn(|| {
+                let guard = lock.lock();
+                held.wait();
+                thread::sleep(Duration::from_millis(20));
+                drop(guard);
+            });
+            held.wait();
+            let start = Instant::now();
+            let guard = lock.try_lock_for_with(2, &backoff);
+            assert!(guard.is_some(), "the release happened within the round");
+            assert!(start.elapsed() < Duration::from_secs(2), "took {:?}", start.elapsed());
+        });
+        assert_eq!(backoff.current(), 1 << 30, "a cut-short round does not escalate");
+    }
+
+    #[test]
+    fn test_new_with_backoff_starts_harder() {
+        use crate::{BackOff, SpinLock};
+
+        let default = SpinLock::new(0u32);
+        let hot = SpinLock::new_with_backoff(0u32, BackOff::START_VALUE << 4);
+        assert_eq!(default.lock_backoff().current(), BackOff::START_VALUE);
+
+        // The first contended round already spins 16 times as long...
+        let first = hot.lock_backoff();
+        assert_eq!(first.current(), BackOff::START_VALUE << 4);
+        first.wait();
+        // ...and escalates from there.
+        assert_eq!(first.current(), BackOff::START_VALUE << 5);
+
+        *hot.lock() += 1;
+        assert_eq!(*hot.lock(), 1);
+
+        // A zero start is clamped to 1 so it still escalates...
+        let zero = SpinLock::new_with_backoff((), 0);
+        let backoff = zero.lock_backoff();
+        assert_eq!(backoff.current(), 1);
+        backoff.wait();
+        assert_eq!(backoff.current(), 2);
+        drop(zero.lock());
+
+        // ...and starts past the cap are clamped to it.
+        let capped = SpinLock::new_with_backoff((), u32::MAX);
+        assert_eq!(capped.backoff_start(), BackOff::MAX_SPIN);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_new_with_backoff_contended_lock() {
+        use crate::SpinLock;
+        use std::thread;
+
+        let lock = SpinLock::new_with_backoff(0u32, 2048);
+        thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| {
+                    for _ in 0..1_000 {
+                        *lock.lock() += 1;
+                    }
+                });
+            }
+        });
+        assert_eq!(*lock.lock(), 4_000);
+    }
+
+    #[test]
+    fn test_try_with_lock_result_releases_on_err() {
+        use crate::SpinLock;
+
+        let lock = SpinLock::new(vec![1, 2]);
+        let pop_two = |lock: &SpinLock<Vec<i32>>| -> Result<i32, usize> {
+            let sum = lock
+                .try_with_lock_result(|v| -> Result<i32, usize> {
+                    let (a, b) = (v.pop().ok_or(v.len())?, v.pop().ok_or(v.len())?);
+                    Ok(a + b)
+                })
+                // Runs as the error propagates: the lock must already be free.
+                .inspect_err(|_| assert!(!lock.is_locked(), "lock held on the error path"))?;
+            Ok(sum)
+        };
+
+        assert_eq!(pop_two(&lock), Ok(3));
+        assert!(!lock.is_locked());
+        assert_eq!(pop_two(&lock), Err(0));
+        assert!(lock.try_lock().is_some());
     }
 
+    #[test]
+    fn test_clone_copies_data_and_settings() {
+        use crate::SpinLock;
+
+        let lock = SpinLock::new_with_backoff(vec![1, 2, 3], 512);
+        let copy = lock.clone();
+        assert_eq!(*copy.lock(), [1, 2, 3]);
+        assert_eq!(copy.backoff_start(), 512);
+        assert!(!lock.is_locked() && !copy.is_locked());
+    }
+
+    #[test]
+    fn test_clone_from_reuses_capacity() {
+        use crate::SpinLock;
+
+        let source = SpinLock::new_low_latency(vec![7u8; 16]);
+        let mut target = SpinLock::new(Vec::with_capacity(1024));
+        target.get_mut().push(1);
+        let buffer = target.get_mut().as_ptr();
+
+        target.clone_from(&source);
+        assert_eq!(*target.lock(), [7u8; 16]);
+        assert!(target.get_mut().capacity() >= 1024, "capacity kept");
+        assert_eq!(target.get_mut().as_ptr(), buffer, "no reallocation");
+        assert!(target.has_config(super::CONFIG_LOW_LATENCY));
+        assert!(!source.is_locked());
+    }
+
+    #[test]
+    fn test_new_adaptive_relaxes_when_uncontended() {
+        use crate::spinlock::MAX_ADAPTIVE_BACKOFF_START;
+        use crate::{BackOff, SpinLock};
+
+        let lock = SpinLock::new_adaptive(0u32);
+        assert_eq!(lock.backoff_start(), BackOff::START_VALUE);
+        #[cfg(feature = "std")]
+        assert_eq!(MAX_ADAPTIVE_BACKOFF_START, BackOff::YIELD_THRESHOLD);
+
+        // A contended acquisition keeps its escalation, within the cap...
+        lock.adapt_backoff_start(2);
+        assert_eq!(lock.backoff_start(), BackOff::START_VALUE << 2);
+        lock.adapt_backoff_start(30);
+        assert_eq!(lock.backoff_start(), MAX_ADAPTIVE_BACKOFF_START);
+
+        // ...and quick ones relax it step by step, down to 1.
+        *lock.lock() += 1;
+        assert_eq!(lock.backoff_start(), MAX_ADAPTIVE_BACKOFF_START / 2);
+        for _ in 0..32 {
+            *lock.lock() += 1;
+        }
+        assert_eq!(lock.backoff_start(), 1);
+        assert_eq!(lock.lock_backoff().current(), 1);
 
-}
\ No newline at end of file
+        // Plain locks never retune.
+        let plain = SpinLock::new_with_backoff(0u32, 64);
+        drop(plain.lock());
+        assert_eq!(plain.backoff_start(), 64);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_new_adaptive_escalates_under_contention() {
+        use crate::{BackOff, SpinLock};
+        use std::sync::Barrier;
+        use std::thread;
+        use std::time::Duration;
+
+        let lock = SpinLock::new_adaptive(0u32);
+        let held = Barrier::new(2);
+        thread::scope(|s| {
+            s.spawn(|| {
+                let guard = lock.lock();
+                held.wait();
+                thread::sleep(Duration::from_millis(20));
+                drop(guard);
+            });
+            held.wait();
+            // Waits for the whole hold, so it backs off for several rounds.
+            *lock.lock() += 1;
+        });
+        assert!(
+            lock.backoff_start() > BackOff::START_VALUE,
+            "start stayed at {}",
+            lock.backoff_start()
+        );
+    }
+
+    #[cfg(all(feature = "recovery", feature = "std"))]
+    #[test]
+    fn test_force_unlock_after_holder_died() {
+        use crate::SpinLock;
+        use std::thread;
+
+        let lock = SpinLock::new(vec![1]);
+        // Safety (here and below): no live guard exists when these run.
+        assert!(!unsafe { lock.force_unlock() }, "nothing to release");
+
+        // The holder exits without ever dropping its guard.
+        thread::scope(|s| {
+            s.spawn(|| {
+                let mut guard = lock.lock();
+                guard.push(2);
+                core::mem::forget(guard);
+            });
+        });
+        assert!(lock.is_locked());
+        assert!(unsafe { lock.force_unlock() }, "reports the stranded holder");
+        assert!(!lock.is_locked());
+        assert!(!unsafe { lock.force_unlock() });
+
+        assert_eq!(*lock.lock(), [1, 2], "data left as the holder wrote it");
+        let seqcst = SpinLock::new_seqcst(());
+        core::mem::forget(seqcst.lock());
+        assert!(unsafe { seqcst.force_unlock() });
+        assert!(seqcst.try_lock().is_some());
+    }
+}
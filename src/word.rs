@@ -0,0 +1,409 @@
+//! # Word-backed spinlocks
+//!
+//! [`SpinLock`](crate::SpinLock) variants whose lock flag is a wider atomic
+//! integer instead of an [`AtomicBool`](core::sync::atomic::AtomicBool):
+//!
+//! - [`WordSpinLock`] — backed by a pointer-sized [`AtomicUsize`].
+//! - [`SpinLockU32`] — backed by an [`AtomicU32`].
+//!
+//! Some embedded targets (e.g. AVR-like or older MCU cores) do not provide 8-bit
+//! read-modify-write atomics, so the default `SpinLock` cannot be built for them.
+//! Those targets usually still offer word-sized atomics, and `WordSpinLock` works
+//! there with exactly the same semantics.
+//!
+//! `SpinLockU32` targets configurations where 32 bits is the natural atomic
+//! width. On WASM, for example, it maps directly onto `i32.atomic.rmw`
+//! instructions and is the width used by `memory.atomic.wait32`/`notify`.
+//!
+//! Both are aliases of the generic [`WideSpinLock`], so they share one
+//! implementation and one guard type ([`WideSpinGuard`]):
+//!
+//! - ✅ Same Acquire/Release protocol as `SpinLock`
+//! - ✅ Same exponential [`BackOff`] under contention
+//! - ✅ RAII guard releasing on drop
+//!
+//! Each alias is available whenever the target has atomics of its width
+//! (`target_has_atomic = "ptr"` / `"32"`).
+//!
+//! ## WASM blocking fallback (`wasm-atomics`)
+//!
+//! Busy-spinning is especially costly on `wasm32`, where a spinning worker pins
+//! a whole agent. With the **`wasm-atomics`** feature enabled, [`SpinLockU32`]
+//! spins with [`BackOff`] for a short budget and then blocks the agent with
+//! `memory.atomic.wait32` until the holder releases; the release path issues a
+//! `memory.atomic.notify` to wake one waiter.
+//!
+//! This only takes effect when compiling for `wasm32` with threads support,
+//! which currently requires a **nightly** toolchain: the wait/notify intrinsics
+//! are unstable (the crate enables `stdarch_wasm_atomic_wait` itself), and
+//! stable rustc never sets `target_feature = "atomics"` for wasm, even with
+//! `+atomics` in `RUSTFLAGS`. So:
+//!
+//! - build on nightly with `RUSTFLAGS="-C target-feature=+atomics,+bulk-memory"`
+//!   and `-Z build-std=std,panic_abort` so `std` is rebuilt with atomics, e.g.
+//!   `cargo +nightly build -Zbuild-std=std,panic_abort --target wasm32-unknown-unknown --features wasm-atomics`,
+//! - link with shared memory (`-C link-arg=--shared-memory`, plus
+//!   `--import-memory` / `--max-memory=<bytes>` as your host requires),
+//! - run on workers: browsers forbid `memory.atomic.wait32` on the main thread.
+//!
+//! On other targets the feature has no effect and the lock spins exactly like
+//! the other variants. On `wasm32` *without* `+atomics` it is rejected at build
+//! time, since it could never block there.
+//!
+//! ## Example
+//! ```rust
+//! use axiom_spinlock::{SpinLockU32, WordSpinLock};
+//!
+//! static COUNTER: WordSpinLock<u32> = WordSpinLock::new(0);
+//! static FLAGS: SpinLockU32<u8> = SpinLockU32::new(0);
+//!
+//! *COUNTER.lock() += 1;
+//! *FLAGS.lock() |= 0b10;
+//! assert_eq!(*COUNTER.lock(), 1);
+//! assert_eq!(*FLAGS.lock(), 0b10);
+//! ```
+
+use core::cell::UnsafeCell;
+use core::ops::{Deref, DerefMut};
+#[cfg(target_has_atomic = "32")]
+use core::sync::atomic::AtomicU32;
+#[cfg(target_has_atomic = "ptr")]
+use core::sync::atomic::AtomicUsize;
+use core::sync::atomic::Ordering::{Acquire, Release};
+
+use crate::BackOff;
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// An atomic integer usable as the flag of a [`WideSpinLock`].
+///
+/// This trait is sealed; it is implemented for [`AtomicU32`] and [`AtomicUsize`]
+/// on targets that support them.
+pub trait LockWord: private::Sealed {
+    /// Attempts to set the flag, returning `true` if it was previously clear.
+    #[doc(hidden)]
+    fn try_acquire(&self) -> bool;
+
+    /// Clears the flag with `Release` ordering.
+    #[doc(hidden)]
+    fn release(&self);
+
+    /// Returns whether the flag is set, with `Acquire` ordering.
+    #[doc(hidden)]
+    fn is_held(&self) -> bool;
+
+    /// Blocks until the flag is (probably) cleared, once the spin budget is spent.
+    ///
+    /// Returns `false` if the platform has no way to block, in which case the
+    /// caller keeps spinning.
+    #[doc(hidden)]
+    #[inline(always)]
+    fn park(&self) -> bool {
+        false
+    }
+}
+
+/// Whether [`SpinLockU32`] blocks via `memory.atomic.wait32` under contention.
+#[cfg(target_has_atomic = "32")]
+const WASM_WAIT: bool = cfg!(all(
+    feature = "wasm-atomics",
+    target_arch = "wasm32",
+    target_feature = "atomics"
+));
+
+/// Backoff rounds a waiter spins before parking, when parking is available.
+const PARK_AFTER_ROUNDS: u32 = 8;
+
+#[cfg(target_has_atomic = "32")]
+impl private::Sealed for AtomicU32 {}
+
+#[cfg(target_has_atomic = "32")]
+impl LockWord for AtomicU32 {
+    #[inline(always)]
+    fn try_acquire(&self) -> bool {
+        self.swap(1, Acquire) == 0
+    }
+
+    #[inline(always)]
+    fn release(&self) {
+        self.store(0, Release);
+        #[cfg(all(feature = "wasm-atomics", target_arch = "wasm32", target_feature = "atomics"))]
+        // Safety: the pointer comes from a live `AtomicU32`, which has the size and
+        // alignment of `i32`.
+        unsafe {
+            core::arch::wasm32::memory_atomic_notify(self.as_ptr().cast(), 1);
+        }
+    }
+
+    #[inline(always)]
+    fn is_held(&self) -> bool {
+        self.load(Acquire) != 0
+    }
+
+    #[inline(always)]
+    fn park(&self) -> bool {
+        #[cfg(all(feature = "wasm-atomics", target_arch = "wasm32", target_feature = "atomics"))]
+        // Safety: as in `release`. A timeout of -1 waits until notified; the wait
+        // returns immediately if the flag is no longer 1.
+        unsafe {
+            core::arch::wasm32::memory_atomic_wait32(self.as_ptr().cast(), 1, -1);
+        }
+        WASM_WAIT
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl private::Sealed for AtomicUsize {}
+
+#[cfg(target_has_atomic = "ptr")]
+impl LockWord for AtomicUsize {
+    #[inline(always)]
+    fn try_acquire(&self) -> bool {
+        self.swap(1, Acquire) == 0
+    }
+
+    #[inline(always)]
+    fn release(&self) {
+        self.store(0, Release)
+    }
+
+    #[inline(always)]
+    fn is_held(&self) -> bool {
+        self.load(Acquire) != 0
+    }
+}
+
+/// A spin-based mutual exclusion primitive generic over its atomic flag.
+///
+/// Behaves exactly like [`SpinLock`](crate::SpinLock). Use it through the
+/// [`WordSpinLock`] or [`SpinLockU32`] aliases, which provide the constructors.
+pub struct WideSpinLock<T, W: LockWord> {
+    data: UnsafeCell<T>,
+    locked: W,
+}
+
+/// A [`WideSpinLock`] backed by a pointer-sized [`AtomicUsize`].
+#[cfg(target_has_atomic = "ptr")]
+pub type WordSpinLock<T> = WideSpinLock<T, AtomicUsize>;
+
+/// A [`WideSpinLock`] backed by an [`AtomicU32`].
+#[cfg(target_has_atomic = "32")]
+pub type SpinLockU32<T> = WideSpinLock<T, AtomicU32>;
+
+/// A guard that releases the [`WideSpinLock`] when dropped.
+///
+/// This is returned from [`WideSpinLock::lock`] and implements [`Deref`] and
+/// [`DerefMut`] to access the underlying data.
+pub struct WideSpinGuard<'a, T, W: LockWord> {
+    guard: &'a WideSpinLock<T, W>,
+}
+
+/// The guard type of [`WordSpinLock`].
+#[cfg(target_has_atomic = "ptr")]
+pub type WordSpinGuard<'a, T> = WideSpinGuard<'a, T, AtomicUsize>;
+
+/// The guard type of [`SpinLockU32`].
+#[cfg(target_has_atomic = "32")]
+pub type SpinGuardU32<'a, T> = WideSpinGuard<'a, T, AtomicU32>;
+
+impl<T, W: LockWord> Drop for WideSpinGuard<'_, T, W> {
+    #[inline]
+    fn drop(&mut self) {
+        self.guard.locked.release()
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<T> WideSpinLock<T, AtomicUsize> {
+    /// Creates a new [`WordSpinLock`] wrapping the given data.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::WordSpinLock;
+    ///
+    /// let lock = WordSpinLock::new(123);
+    /// assert_eq!(*lock.lock(), 123);
+    /// ```
+    #[inline(always)]
+    pub const fn new(data: T) -> Self {
+        WideSpinLock {
+            data: UnsafeCell::new(data),
+            locked: AtomicUsize::new(0),
+        }
+    }
+}
+
+#[cfg(target_has_atomic = "32")]
+impl<T> WideSpinLock<T, AtomicU32> {
+    /// Creates a new [`SpinLockU32`] wrapping the given data.
+    ///
+    /// # Example
+    /// ```
+    /// use axiom_spinlock::SpinLockU32;
+    ///
+    /// let lock = SpinLockU32::new(123);
+    /// assert_eq!(*lock.lock(), 123);
+    /// ```
+    #[inline(always)]
+    pub const fn new(data: T) -> Self {
+        WideSpinLock {
+            data: UnsafeCell::new(data),
+            locked: AtomicU32::new(0),
+        }
+    }
+}
+
+impl<T, W: LockWord> WideSpinLock<T, W> {
+    /// Acquires the lock, spinning until it becomes available.
+    ///
+    /// Uses an exponential [`BackOff`] to reduce contention. With the
+    /// `wasm-atomics` feature on `wasm32`, [`SpinLockU32`] blocks the agent
+    /// after a short spin budget instead of spinning indefinitely.
+    #[inline]
+    pub fn lock(&self) -> WideSpinGuard<'_, T, W> {
+        let backoff = BackOff::new();
+        let mut rounds: u32 = 0;
+        while !self.locked.try_acquire() {
+            if rounds >= PARK_AFTER_ROUNDS && self.locked.park() {
+                continue;
+            }
+            rounds = rounds.saturating_add(1);
+            backoff.wait();
+        }
+
+        WideSpinGuard { guard: self }
+    }
+
+    /// Unsafely releases the lock manually.
+    ///
+    /// # Safety
+    /// - Only call this if you *own* the lock.
+    /// - Misuse can cause data races or UB.
+    #[inline]
+    pub unsafe fn unlock(&self) {
+        self.locked.release();
+    }
+
+    /// Attempts to acquire the lock without blocking.
+    ///
+    /// Returns `Some(guard)` if the lock was free, or `None` otherwise.
+    #[inline]
+    pub fn try_lock(&self) -> Option<WideSpinGuard<'_, T, W>> {
+        if self.locked.try_acquire() {
+            Some(WideSpinGuard { guard: self })
+        } else {
+            None
+        }
+    }
+
+    /// Checks whether the lock is currently held.
+    #[inline(always)]
+    pub fn is_locked(&self) -> bool {
+        self.locked.is_held()
+    }
+
+    /// Tries to acquire the lock within a fixed number of spin attempts.
+    ///
+    /// Returns `Some(guard)` if successful, otherwise `None` after the given number of spins.
+    #[inline]
+    pub fn try_lock_for(&self, spins: usize) -> Option<WideSpinGuard<'_, T, W>> {
+        let backoff = BackOff::new();
+        for _ in 0..spins {
+            if self.locked.try_acquire() {
+                return Some(WideSpinGuard { guard: self });
+            }
+            backoff.wait();
+        }
+        None
+    }
+
+    /// Runs a closure with exclusive access to the data.
+    #[inline]
+    pub fn with_lock<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        let mut guard = self.lock();
+        f(&mut *guard)
+    }
+}
+
+impl<T, W: LockWord> Deref for WideSpinGuard<'_, T, W> {
+    type Target = T;
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        unsafe { &*(self.guard.data.get()) }
+    }
+}
+
+impl<T, W: LockWord> DerefMut for WideSpinGuard<'_, T, W> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *self.guard.data.get() }
+    }
+}
+
+// Safety: WideSpinLock enforces mutual exclusion via atomic operations.
+unsafe impl<T: Send, W: LockWord + Send> Send for WideSpinLock<T, W> {}
+unsafe impl<T: Send, W: LockWord + Sync> Sync for WideSpinLock<T, W> {}
+
+#[cfg(test)]
+mod test {
+    #[cfg(target_has_atomic = "ptr")]
+    #[test]
+    fn test_basic_lock_unlock() {
+        use crate::WordSpinLock;
+
+        let lock = WordSpinLock::new(10);
+
+        {
+            let mut guard = lock.lock();
+            *guard += 5;
+            assert_eq!(*guard, 15);
+            assert!(lock.try_lock().is_none(), "Lock should not be acquirable while held");
+        }
+
+        assert!(!lock.is_locked(), "Lock should be released after guard drop");
+    }
+
+    #[cfg(target_has_atomic = "32")]
+    #[test]
+    fn test_u32_lock_matches_default_behavior() {
+        use crate::SpinLockU32;
+
+        let lock = SpinLockU32::new(1u64);
+
+        let guard = lock.lock();
+        assert!(lock.is_locked());
+        assert!(lock.try_lock_for(4).is_none(), "Lock should not be acquirable while held");
+        drop(guard);
+
+        lock.with_lock(|v| *v += 1);
+        assert_eq!(*lock.try_lock().expect("Lock should be free"), 2);
+    }
+
+    #[cfg(all(feature = "std", target_has_atomic = "ptr"))]
+    #[test]
+    fn test_concurrent_access() {
+        use crate::WordSpinLock;
+        use std::sync::Arc;
+        use std::thread;
+
+        let lock = Arc::new(WordSpinLock::new(0usize));
+        let mut handles = vec![];
+
+        for _ in 0..8 {
+            let lock_cloned = lock.clone();
+            handles.push(thread::spawn(move || {
+                for _ in 0..10_000 {
+                    *lock_cloned.lock() += 1;
+                }
+            }));
+        }
+
+        for h in handles {
+            h.join().unwrap();
+        }
+
+        assert_eq!(*lock.lock(), 8 * 10_000, "Counter should match total increments");
+    }
+}
@@ -0,0 +1,142 @@
+//! # validate
+//!
+//! Memory-ordering checks meant to be run under ThreadSanitizer, where an
+//! ordering mistake in the lock shows up as a reported data race on the
+//! (deliberately non-atomic) protected data.
+//!
+//! The tests also run, and pass, as a regular integration test. Under TSan:
+//!
+//! ```text
+//! RUSTFLAGS="-Z sanitizer=thread --cfg tsan" RUSTDOCFLAGS="-Z sanitizer=thread" \
+//!     cargo +nightly test -Z build-std --target x86_64-unknown-linux-gnu --test validate
+//! ```
+//!
+//! `--cfg tsan` only shrinks the iteration counts, since TSan slows every
+//! memory access down considerably.
+//!
+//! ## Ordering review
+//!
+//! - Acquisition is `swap(true, Acquire)`: a *successful* swap reads the `false`
+//!   stored by the previous `unlock` (`Release`), so the new holder sees all of
+//!   the previous holder's writes. No `AcqRel` is needed, because acquiring
+//!   publishes nothing.
+//! - A *failed* `try_lock` reads `true`. It is an RMW in the release sequence of
+//!   the last `unlock`, so it may synchronize with the **previous** holder, but
+//!   it creates **no** happens-before edge with the **current** holder, whose
+//!   acquiring swap is not a release. Accessing the data after a failed
+//!   `try_lock` is therefore a data race, which
+//!   [`failed_try_lock_gives_no_access`] documents.
+
+use axiom_spinlock::{RawSpinLock, SpinLock};
+use std::cell::UnsafeCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+#[cfg(not(tsan))]
+const ITERATIONS: usize = 20_000;
+#[cfg(tsan)]
+const ITERATIONS: usize = 1_000;
+
+const THREADS: usize = 4;
+
+/// The crate's concurrent increment scenario: any missing Acquire/Release pair
+/// makes TSan report the racing `+= 1` on the `u64`.
+#[test]
+fn concurrent_increment_is_race_free() {
+    let lock = Arc::new(SpinLock::new(0u64));
+    let handles: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let lock = lock.clone();
+            thread::spawn(move || {
+                for _ in 0..ITERATIONS {
+                    *lock.lock() += 1;
+                }
+            })
+        })
+        .collect();
+    for h in handles {
+        h.join().unwrap();
+    }
+    assert_eq!(*lock.lock(), (THREADS * ITERATIONS) as u64);
+}
+
+/// Same as above through `try_lock`/`RawSpinLock`, which share the swap but not
+/// the guard code path.
+#[test]
+fn raw_try_lock_is_race_free() {
+    struct Shared {
+        lock: RawSpinLock,
+        value: UnsafeCell<u64>,
+    }
+    unsafe impl Sync for Shared {}
+
+    let shared = Arc::new(Shared {
+        lock: RawSpinLock::new(),
+        value: UnsafeCell::new(0),
+    });
+    let handles: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let shared = shared.clone();
+            thread::spawn(move || {
+                let mut done = 0;
+                while done < ITERATIONS {
+                    if shared.lock.try_lock() {
+                        unsafe {
+                            *shared.value.get() += 1;
+                            shared.lock.unlock();
+                        }
+                        done += 1;
+                    }
+                }
+            })
+        })
+        .collect();
+    for h in handles {
+        h.join().unwrap();
+    }
+    shared.lock.lock();
+    assert_eq!(unsafe { *shared.value.get() }, (THREADS * ITERATIONS) as u64);
+    unsafe { shared.lock.unlock() };
+}
+
+/// A failed `try_lock` establishes no happens-before edge with the current
+/// holder, so it must grant no access: it returns `None` (no reference to the
+/// data exists), leaves the holder's state untouched, and the holder's writes
+/// only become visible through a later *successful* acquisition.
+///
+/// The holder writes while the observer's attempts fail; the observer only reads
+/// after acquiring. Reading inside the failure branch instead would be the data
+/// race TSan reports.
+#[test]
+fn failed_try_lock_gives_no_access() {
+    let lock = Arc::new(SpinLock::new(0u64));
+    let barrier = Arc::new(Barrier::new(2));
+    let observer_failed = Arc::new(AtomicBool::new(false));
+
+    let holder = {
+        let (lock, barrier, observer_failed) =
+            (lock.clone(), barrier.clone(), observer_failed.clone());
+        thread::spawn(move || {
+            let mut guard = lock.lock();
+            barrier.wait();
+            // Keep writing while the observer is failing to acquire.
+            while !observer_failed.load(Ordering::Relaxed) {
+                *guard = guard.wrapping_add(1);
+                std::hint::spin_loop();
+            }
+            *guard = u64::MAX;
+        })
+    };
+
+    barrier.wait();
+    for _ in 0..ITERATIONS {
+        assert!(lock.try_lock().is_none(), "Held lock must not be acquired");
+    }
+    assert!(lock.is_locked(), "Failed attempts must not disturb the holder");
+    observer_failed.store(true, Ordering::Relaxed);
+
+    // Only a successful acquisition synchronizes with the holder's release.
+    assert_eq!(*lock.lock(), u64::MAX);
+    holder.join().unwrap();
+}